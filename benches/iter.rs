@@ -0,0 +1,51 @@
+use cascada::{EmptyLayout, HorizontalLayout, Layout};
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::alloc::{GlobalAlloc, Layout as AllocLayout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Counts the bytes allocated, to compare the memory used by the iterators.
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: AllocLayout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: AllocLayout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: AllocLayout, new_size: usize) -> *mut u8 {
+        ALLOCATED.fetch_add(new_size, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocated_by(f: impl FnOnce() -> usize) -> usize {
+    let start = ALLOCATED.load(Ordering::Relaxed);
+    std::hint::black_box(f());
+    ALLOCATED.load(Ordering::Relaxed) - start
+}
+
+pub fn benchmark(c: &mut Criterion) {
+    let children = (0..10_000).map(|_| EmptyLayout::new().boxed()).collect();
+    let layout = HorizontalLayout::new().add_boxed_children(children);
+
+    let eager = allocated_by(|| layout.iter().count());
+    let lazy = allocated_by(|| layout.iter_lazy().count());
+    println!("iter allocated {eager} bytes, iter_lazy allocated {lazy} bytes");
+
+    let mut g = c.benchmark_group("iter");
+    g.bench_function("iter", |b| b.iter(|| layout.iter().count()));
+    g.bench_function("iter_lazy", |b| b.iter(|| layout.iter_lazy().count()));
+    g.finish();
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
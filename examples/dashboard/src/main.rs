@@ -1,6 +1,6 @@
 use cascada::debug::DebugTree;
 use cascada::{
-    BoxSizing, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, Padding, Size, VerticalLayout,
+    BoxSizing, EmptyLayout, HorizontalLayout, IntrinsicSize, Padding, Size, VerticalLayout,
     solve_layout,
 };
 
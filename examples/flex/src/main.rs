@@ -1,7 +1,6 @@
 use cascada::debug::DebugTree;
 use cascada::{
-    AxisAlignment, BlockLayout, EmptyLayout, HorizontalLayout, IntrinsicSize, Padding, Size,
-    solve_layout,
+    BlockLayout, EmptyLayout, HorizontalLayout, IntrinsicSize, Padding, Size, solve_layout,
 };
 
 fn main() {
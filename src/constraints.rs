@@ -1,4 +1,5 @@
-use crate::Size;
+use crate::{Padding, Size};
+use std::sync::Arc;
 
 /// Describes the size a [`Layout`] will try to be.
 #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
@@ -12,6 +13,113 @@ pub enum BoxSizing {
     /// Tries to be as big as possible, the behaviour of the flex factor is
     /// dependent on the type of layout.
     Flex(u8),
+    /// The same as [`Flex`](BoxSizing::Flex) but with a fractional flex
+    /// factor, so ratios like `1.5:1` don't need to be scaled.
+    FlexF(f32),
+}
+
+impl BoxSizing {
+    /// Returns the flex factor, or `0.0` if the sizing doesn't flex.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::BoxSizing;
+    ///
+    /// assert_eq!(BoxSizing::Flex(2).flex_factor(), 2.0);
+    /// assert_eq!(BoxSizing::FlexF(1.5).flex_factor(), 1.5);
+    /// assert_eq!(BoxSizing::Shrink.flex_factor(), 0.0);
+    /// ```
+    pub const fn flex_factor(&self) -> f32 {
+        match self {
+            Self::Flex(factor) => *factor as f32,
+            Self::FlexF(factor) => factor.max(0.0),
+            Self::Fixed(_) | Self::Shrink => 0.0,
+        }
+    }
+}
+
+/// Describes how a container that shrinks on its cross axis gets its
+/// cross axis size from its children.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub enum CrossSizing {
+    /// Use the size of the largest child.
+    #[default]
+    Max,
+    /// Use the size of the first child.
+    MatchFirst,
+    /// Use the size of the last child.
+    MatchLast,
+    /// Use a fixed size, regardless of the children.
+    Fixed(f32),
+    /// Use the size of the second largest child, so that a single outlier
+    /// doesn't decide the size. With one child its size is used.
+    SecondLargest,
+    /// Use the size at a percentile of the children's sizes, where `50.0`
+    /// is the median and `100.0` is the largest child.
+    Percentile(f32),
+}
+
+impl CrossSizing {
+    /// Get the cross axis size from the children's minimum cross axis `sizes`.
+    pub(crate) fn resolve(&self, sizes: &[f32]) -> f32 {
+        match self {
+            Self::Max => sizes.iter().copied().fold(0.0, f32::max),
+            Self::MatchFirst => sizes.first().copied().unwrap_or_default(),
+            Self::MatchLast => sizes.last().copied().unwrap_or_default(),
+            Self::Fixed(size) => *size,
+            Self::SecondLargest => {
+                let mut sizes = sizes.to_vec();
+                sizes.sort_by(|a, b| b.total_cmp(a));
+                sizes.get(1).or(sizes.first()).copied().unwrap_or_default()
+            }
+            Self::Percentile(percentile) => {
+                if sizes.is_empty() {
+                    return 0.0;
+                }
+                let mut sizes = sizes.to_vec();
+                sizes.sort_by(f32::total_cmp);
+                // The nearest rank, the smallest size that is at least as
+                // large as the percentile of sizes
+                let rank = (percentile.clamp(0.0, 100.0) / 100.0 * sizes.len() as f32).ceil();
+                sizes[(rank as usize).saturating_sub(1)]
+            }
+        }
+    }
+}
+
+/// Describes how big a container that flexes on its main axis is on
+/// that axis.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub enum MainAxisSize {
+    /// Fill all the space given by the parent.
+    #[default]
+    Max,
+    /// Take the space given by the parent, but only be as big as the
+    /// children, like a container that shrinks.
+    Min,
+}
+
+/// A size that is computed from the available space when the layout
+/// is solved.
+#[derive(Clone)]
+pub(crate) struct ComputedSize(Arc<dyn Fn(f32) -> f32 + Send + Sync>);
+
+impl ComputedSize {
+    pub(crate) fn new(f: impl Fn(f32) -> f32 + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    /// Compute the size from the available space, the size is never
+    /// negative.
+    pub(crate) fn resolve(&self, available: f32) -> f32 {
+        (self.0)(available).max(0.0)
+    }
+}
+
+impl std::fmt::Debug for ComputedSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ComputedSize")
+    }
 }
 
 /// Describes the maximum and minimum size of a [`Layout`].
@@ -37,6 +145,101 @@ impl BoxConstraints {
             min_width: 0.0,
         }
     }
+
+    /// Create [`BoxConstraints`] where the minimum and maximum
+    /// are both the `size`.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{BoxConstraints,Size};
+    ///
+    /// let constraints = BoxConstraints::tight(Size::new(20.0,50.0));
+    ///
+    /// assert_eq!(constraints.min_width,20.0);
+    /// assert_eq!(constraints.max_width,Some(20.0));
+    /// assert_eq!(constraints.min_height,constraints.max_height);
+    /// ```
+    pub const fn tight(size: Size) -> Self {
+        Self {
+            max_width: Some(size.width),
+            max_height: size.height,
+            min_width: size.width,
+            min_height: size.height,
+        }
+    }
+
+    /// Create [`BoxConstraints`] with a minimum of zero and
+    /// a maximum of `size`.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{BoxConstraints,Size};
+    ///
+    /// let constraints = BoxConstraints::loose(Size::new(20.0,50.0));
+    ///
+    /// assert_eq!(constraints.min_width,0.0);
+    /// assert_eq!(constraints.max_width,Some(20.0));
+    /// assert_eq!(constraints.max_height,50.0);
+    /// ```
+    pub const fn loose(size: Size) -> Self {
+        Self {
+            max_width: Some(size.width),
+            max_height: size.height,
+            min_width: 0.0,
+            min_height: 0.0,
+        }
+    }
+
+    /// Returns new [`BoxConstraints`] that respect `other`, by clamping
+    /// each of these constraints to be within `other`.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{BoxConstraints,Size};
+    ///
+    /// let constraints = BoxConstraints::loose(Size::unit(500.0))
+    ///     .enforce(&BoxConstraints::tight(Size::unit(100.0)));
+    ///
+    /// assert_eq!(constraints,BoxConstraints::tight(Size::unit(100.0)));
+    /// ```
+    pub fn enforce(&self, other: &BoxConstraints) -> BoxConstraints {
+        let clamp_width = |width: f32| {
+            let width = width.max(other.min_width);
+            match other.max_width {
+                Some(max_width) => width.min(max_width),
+                None => width,
+            }
+        };
+        let clamp_height = |height: f32| height.max(other.min_height).min(other.max_height);
+
+        Self {
+            max_width: self.max_width.map(clamp_width).or(other.max_width),
+            max_height: clamp_height(self.max_height),
+            min_width: clamp_width(self.min_width),
+            min_height: clamp_height(self.min_height),
+        }
+    }
+
+    /// Clamps a [`Size`] to be within the constraints. A max width of
+    /// `None` leaves the width unbounded.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{BoxConstraints,Size};
+    ///
+    /// let constraints = BoxConstraints::loose(Size::unit(100.0));
+    ///
+    /// assert_eq!(constraints.constrain(Size::new(250.0,50.0)),Size::new(100.0,50.0));
+    /// ```
+    pub fn constrain(&self, size: Size) -> Size {
+        let mut width = size.width;
+        if let Some(max_width) = self.max_width {
+            width = width.min(max_width);
+        }
+        let height = size.height.min(self.max_height);
+
+        Size::new(width.max(self.min_width), height.max(self.min_height))
+    }
 }
 
 /// This is the preferred size of a [`Layout`] node.
@@ -67,6 +270,42 @@ impl IntrinsicSize {
         }
     }
 
+    /// Creates an [`IntrinsicSize`] that fills the width and shrinks the height.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{BoxSizing,IntrinsicSize};
+    ///
+    /// let intrinsic_size = IntrinsicSize::fill_width();
+    ///
+    /// assert_eq!(intrinsic_size.width,BoxSizing::Flex(1));
+    /// assert_eq!(intrinsic_size.height,BoxSizing::Shrink);
+    /// ```
+    pub const fn fill_width() -> Self {
+        Self {
+            width: BoxSizing::Flex(1),
+            height: BoxSizing::Shrink,
+        }
+    }
+
+    /// Creates an [`IntrinsicSize`] that fills the height and shrinks the width.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{BoxSizing,IntrinsicSize};
+    ///
+    /// let intrinsic_size = IntrinsicSize::fill_height();
+    ///
+    /// assert_eq!(intrinsic_size.width,BoxSizing::Shrink);
+    /// assert_eq!(intrinsic_size.height,BoxSizing::Flex(1));
+    /// ```
+    pub const fn fill_height() -> Self {
+        Self {
+            width: BoxSizing::Shrink,
+            height: BoxSizing::Flex(1),
+        }
+    }
+
     /// Creates an intrinsic size with a flex factor.
     ///
     /// # Example
@@ -122,6 +361,79 @@ impl IntrinsicSize {
             height: BoxSizing::Fixed(height),
         }
     }
+
+    /// Creates an [`IntrinsicSize`] with a fixed width that shrinks the height.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{IntrinsicSize,BoxSizing};
+    ///
+    /// let intrinsic_size = IntrinsicSize::fixed_width(100.0);
+    ///
+    /// assert_eq!(intrinsic_size.width,BoxSizing::Fixed(100.0));
+    /// assert_eq!(intrinsic_size.height,BoxSizing::Shrink);
+    /// ```
+    pub const fn fixed_width(width: f32) -> Self {
+        Self {
+            width: BoxSizing::Fixed(width),
+            height: BoxSizing::Shrink,
+        }
+    }
+
+    /// Creates an [`IntrinsicSize`] with a fixed height that shrinks the width.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{IntrinsicSize,BoxSizing};
+    ///
+    /// let intrinsic_size = IntrinsicSize::fixed_height(50.0);
+    ///
+    /// assert_eq!(intrinsic_size.width,BoxSizing::Shrink);
+    /// assert_eq!(intrinsic_size.height,BoxSizing::Fixed(50.0));
+    /// ```
+    pub const fn fixed_height(height: f32) -> Self {
+        Self {
+            width: BoxSizing::Shrink,
+            height: BoxSizing::Fixed(height),
+        }
+    }
+
+    /// Creates a fixed [`IntrinsicSize`] that fits the `content` size
+    /// plus the [`Padding`].
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{IntrinsicSize,BoxSizing,Padding,Size};
+    ///
+    /// let intrinsic_size = IntrinsicSize::fixed_with_padding(
+    ///     Size::new(100.0,50.0),
+    ///     Padding::all(10.0)
+    /// );
+    ///
+    /// assert_eq!(intrinsic_size.width,BoxSizing::Fixed(120.0));
+    /// assert_eq!(intrinsic_size.height,BoxSizing::Fixed(70.0));
+    /// ```
+    pub const fn fixed_with_padding(content: Size, padding: Padding) -> Self {
+        Self::fixed(
+            content.width + padding.horizontal_sum(),
+            content.height + padding.vertical_sum(),
+        )
+    }
+}
+
+impl IntrinsicSize {
+    /// Replaces any [`BoxSizing::Shrink`] axis with the `default` sizing.
+    pub(crate) const fn or_default(self, default: BoxSizing) -> Self {
+        let width = match self.width {
+            BoxSizing::Shrink => default,
+            width => width,
+        };
+        let height = match self.height {
+            BoxSizing::Shrink => default,
+            height => height,
+        };
+        Self { width, height }
+    }
 }
 
 impl From<Size> for IntrinsicSize {
@@ -138,6 +450,15 @@ macro_rules! impl_constraints {
         /// Sets the maximum width of the given layout.
         pub fn max_width(mut self, width: f32) -> Self {
             self.constraints.max_width = Some(width);
+            self.configured_max_width = Some(width);
+            self
+        }
+
+        /// Marks the layout node as auto sized. Any axis that is left as
+        /// [`BoxSizing::Shrink`](crate::BoxSizing::Shrink) will use the default sizing
+        /// passed to [`solve_layout_with_defaults`](crate::solve_layout_with_defaults).
+        pub fn auto(mut self) -> Self {
+            self.auto = true;
             self
         }
 
@@ -146,7 +467,101 @@ macro_rules! impl_constraints {
             self.intrinsic_size = intrinsic_size;
             self
         }
+
+        /// Finishes building the layout node, returning an error if the
+        /// configuration contradicts itself, for example a fixed width
+        /// that is larger than the max width.
+        pub fn build(self) -> Result<Self, $crate::LayoutError> {
+            if let ($crate::BoxSizing::Fixed(width), Some(max_width)) =
+                (self.intrinsic_size.width, self.configured_max_width)
+                && width > max_width
+            {
+                return Err($crate::LayoutError::conflicting_constraints(self.id));
+            }
+            Ok(self)
+        }
     };
 }
 
 pub(crate) use impl_constraints;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{EmptyLayout, Layout, LayoutError};
+
+    #[test]
+    fn cross_sizing_percentile() {
+        let sizes = [10.0, 40.0, 20.0, 30.0, 500.0];
+        assert_eq!(CrossSizing::Percentile(50.0).resolve(&sizes), 30.0);
+        assert_eq!(CrossSizing::Percentile(80.0).resolve(&sizes), 40.0);
+        assert_eq!(CrossSizing::Percentile(100.0).resolve(&sizes), 500.0);
+        assert_eq!(CrossSizing::Percentile(0.0).resolve(&sizes), 10.0);
+        assert_eq!(CrossSizing::Percentile(50.0).resolve(&[]), 0.0);
+    }
+
+    #[test]
+    fn fixed_width_larger_than_max_width() {
+        let layout = EmptyLayout::new()
+            .intrinsic_size(IntrinsicSize::fixed(200.0, 100.0))
+            .max_width(150.0);
+        let id = layout.id();
+        let err = layout.build().unwrap_err();
+        assert_eq!(err, LayoutError::conflicting_constraints(id));
+
+        let layout = EmptyLayout::new()
+            .intrinsic_size(IntrinsicSize::fixed(100.0, 100.0))
+            .max_width(150.0);
+        assert!(layout.build().is_ok());
+    }
+
+    #[test]
+    fn constrain_above_max() {
+        let constraints = BoxConstraints {
+            min_width: 10.0,
+            min_height: 10.0,
+            max_width: Some(100.0),
+            max_height: 200.0,
+        };
+
+        let size = constraints.constrain(Size::new(150.0, 250.0));
+        assert_eq!(size, Size::new(100.0, 200.0));
+    }
+
+    #[test]
+    fn constrain_below_min() {
+        let constraints = BoxConstraints {
+            min_width: 10.0,
+            min_height: 20.0,
+            max_width: Some(100.0),
+            max_height: 200.0,
+        };
+
+        let size = constraints.constrain(Size::new(5.0, 0.0));
+        assert_eq!(size, Size::new(10.0, 20.0));
+    }
+
+    #[test]
+    fn constrain_unbounded_width() {
+        let constraints = BoxConstraints::new();
+        let size = constraints.constrain(Size::new(5000.0, 0.0));
+        assert_eq!(size.width, 5000.0);
+    }
+
+    #[test]
+    fn enforce_tight_constraints() {
+        let tight = BoxConstraints::tight(Size::new(40.0, 60.0));
+        let constraints = BoxConstraints::loose(Size::unit(500.0)).enforce(&tight);
+        assert_eq!(constraints, tight);
+    }
+
+    #[test]
+    fn enforce_keeps_unset_max_width() {
+        let constraints = BoxConstraints::default().enforce(&BoxConstraints::default());
+        assert_eq!(constraints.max_width, None);
+
+        let loose = BoxConstraints::loose(Size::unit(300.0));
+        let constraints = BoxConstraints::default().enforce(&loose);
+        assert_eq!(constraints.max_width, Some(300.0));
+    }
+}
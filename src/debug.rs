@@ -1,28 +1,274 @@
-use crate::Layout;
+use crate::{BoxConstraints, Layout, Position, Size};
+use std::fmt::Write;
+
+/// How nodes are labelled in [`DebugTree::debug_string`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DebugStyle {
+    /// Use the full label of each node, such as `VerticalLayout`.
+    #[default]
+    Full,
+    /// Use a one letter tag of the node type, such as `V`, unless the node
+    /// has a label set by the user. This keeps the output of large trees
+    /// short.
+    Compact,
+}
 
 pub trait DebugTree: Layout {
+    /// Prints the children of the node, with each child indented under
+    /// its parent starting at `indent`.
     fn print_children(&self, indent: usize) {
-        for node in self.children() {
-            node.print(indent);
-            node.print_children(indent + 1);
+        let mut output = String::new();
+        for child in self.children() {
+            write_tree(&mut output, child.as_ref(), indent, DebugStyle::Full);
         }
+        print!("{output}");
     }
 
+    /// Prints the size and position of the node, indented by `indent`.
     fn print(&self, indent: usize) {
-        let size = self.size();
-        let position = self.position();
-        let label = self.label();
-        let whitespace = " ".repeat(indent);
-        println!("{whitespace}•{label}(size: {size}, position: {position})");
+        let mut output = String::new();
+        write_node(
+            &mut output,
+            indent,
+            &self.label(),
+            self.size(),
+            self.position(),
+        );
+        print!("{output}");
     }
 
-    // TODO: only expose one method
+    /// Prints the size and position of each node in the tree, with each
+    /// child indented under its parent.
     fn debug_tree(&self) {
-        self.print(0);
-        self.print_children(1);
+        print!("{}", self.debug_string(DebugStyle::Full));
+    }
+
+    /// Returns the size and position of each node in the tree, with each
+    /// child indented under its parent, in the same format as
+    /// [`debug_tree`](DebugTree::debug_tree).
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::debug::{DebugStyle, DebugTree};
+    /// use cascada::{solve_layout, EmptyLayout, VerticalLayout, Size};
+    ///
+    /// let mut layout = VerticalLayout::new().add_child(EmptyLayout::new());
+    /// solve_layout(&mut layout, Size::unit(500.0));
+    ///
+    /// let output = layout.debug_string(DebugStyle::Compact);
+    /// assert_eq!(output, "•V(size: 0x0, position: 0x0)\n •E(size: 0x0, position: 0x0)\n");
+    /// ```
+    fn debug_string(&self, style: DebugStyle) -> String {
+        let mut output = String::new();
+        let label = match style {
+            DebugStyle::Full => self.label(),
+            DebugStyle::Compact => self.short_label(),
+        };
+        write_node(&mut output, 0, &label, self.size(), self.position());
+        for child in self.children() {
+            write_tree(&mut output, child.as_ref(), 1, style);
+        }
+        output
+    }
+
+    /// Returns the solved [`BoxConstraints`] and size of each node in
+    /// the tree, with each child indented under its parent.
+    fn debug_constraints(&self) -> String {
+        let mut output = String::new();
+        write_constraints(
+            &mut output,
+            0,
+            &self.label(),
+            self.size(),
+            self.constraints(),
+        );
+        for child in self.children() {
+            write_tree_constraints(&mut output, child.as_ref(), 1);
+        }
+        output
+    }
+
+    /// Draws the solved tree as a grid of box drawing characters, with
+    /// `cols` characters per line and `rows` lines. The root is scaled to
+    /// fill the grid and each node is drawn as a box, children are drawn
+    /// over their parents.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::debug::DebugTree;
+    /// use cascada::{solve_layout, EmptyLayout, IntrinsicSize, Size};
+    ///
+    /// let mut layout = EmptyLayout::new().intrinsic_size(IntrinsicSize::fill());
+    /// solve_layout(&mut layout, Size::new(400.0, 300.0));
+    ///
+    /// assert_eq!(layout.to_ascii(4, 3), "┌──┐\n│  │\n└──┘");
+    /// ```
+    fn to_ascii(&self, cols: usize, rows: usize) -> String {
+        let mut grid = vec![vec![' '; cols]; rows];
+        let size = self.size();
+        let empty = cols == 0 || rows == 0 || size.width <= 0.0 || size.height <= 0.0;
+        let nodes = if empty { None } else { Some(self.iter()) };
+
+        let origin = self.position();
+        let scale_x = cols as f32 / size.width;
+        let scale_y = rows as f32 / size.height;
+        // The first and last cell that an edge covers
+        let cells = |start: f32, end: f32, scale: f32, count: usize| {
+            let first = (start * scale).floor().max(0.0) as usize;
+            let last = ((end * scale).ceil() as usize).clamp(1, count) - 1;
+            (first.min(count - 1), last)
+        };
+
+        for node in nodes.into_iter().flatten() {
+            let bounds = node.bounds();
+            let (left, right) = cells(
+                bounds.x[0] - origin.x,
+                bounds.x[1] - origin.x,
+                scale_x,
+                cols,
+            );
+            let (top, bottom) = cells(
+                bounds.y[0] - origin.y,
+                bounds.y[1] - origin.y,
+                scale_y,
+                rows,
+            );
+            if right < left || bottom < top {
+                continue;
+            }
+
+            grid[top][left..=right].fill('─');
+            grid[bottom][left..=right].fill('─');
+            for row in &mut grid[top..=bottom] {
+                row[left] = '│';
+                row[right] = '│';
+            }
+            grid[top][left] = '┌';
+            grid[top][right] = '┐';
+            grid[bottom][left] = '└';
+            grid[bottom][right] = '┘';
+        }
+
+        grid.iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn write_tree(output: &mut String, node: &dyn Layout, indent: usize, style: DebugStyle) {
+    let label = match style {
+        DebugStyle::Full => node.label(),
+        DebugStyle::Compact => node.short_label(),
+    };
+    write_node(output, indent, &label, node.size(), node.position());
+    for child in node.children() {
+        write_tree(output, child.as_ref(), indent + 1, style);
     }
 }
 
+fn write_node(output: &mut String, indent: usize, label: &str, size: Size, position: Position) {
+    let whitespace = " ".repeat(indent);
+    let _ = writeln!(
+        output,
+        "{whitespace}•{label}(size: {size}, position: {position})"
+    );
+}
+
+fn write_tree_constraints(output: &mut String, node: &dyn Layout, indent: usize) {
+    write_constraints(
+        output,
+        indent,
+        &node.label(),
+        node.size(),
+        node.constraints(),
+    );
+    for child in node.children() {
+        write_tree_constraints(output, child.as_ref(), indent + 1);
+    }
+}
+
+fn write_constraints(
+    output: &mut String,
+    indent: usize,
+    label: &str,
+    size: Size,
+    constraints: BoxConstraints,
+) {
+    let whitespace = " ".repeat(indent);
+    let max_width = match constraints.max_width {
+        Some(width) => width.to_string(),
+        None => "none".to_string(),
+    };
+    let _ = writeln!(
+        output,
+        "{whitespace}•{label}(size: {size}, min: {}x{}, max: {max_width}x{})",
+        constraints.min_width, constraints.min_height, constraints.max_height
+    );
+}
+
 impl<L: Layout> DebugTree for L {}
 
 impl DebugTree for dyn Layout {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        BlockLayout, EmptyLayout, HorizontalLayout, IntrinsicSize, VerticalLayout, solve_layout,
+    };
+
+    #[test]
+    fn compact_debug_string() {
+        let mut layout = HorizontalLayout::new()
+            .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(20.0, 20.0)))
+            .add_child(BlockLayout::new(EmptyLayout::new()).with_label("Card"))
+            .add_child(VerticalLayout::new());
+        solve_layout(&mut layout, Size::new(800.0, 600.0));
+
+        let output = layout.debug_string(DebugStyle::Compact);
+        let labels: Vec<&str> = output
+            .lines()
+            .map(|line| line.trim_start().split('(').next().unwrap())
+            .collect();
+        assert_eq!(labels, ["•H", "•E", "•Card", "•E", "•V"]);
+
+        let output = layout.debug_string(DebugStyle::Full);
+        assert!(output.starts_with("•HorizontalLayout(size: 20x20"));
+    }
+
+    #[test]
+    fn ascii_two_panes() {
+        let pane = || EmptyLayout::new().intrinsic_size(IntrinsicSize::fill());
+        let mut layout = HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fill())
+            .add_children([pane(), pane()]);
+        solve_layout(&mut layout, Size::new(800.0, 600.0));
+
+        let output = layout.to_ascii(40, 12);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 12);
+        assert!(lines.iter().all(|line| line.chars().count() == 40));
+        assert!(lines[0].starts_with('┌'));
+        assert!(lines[0].ends_with('┐'));
+        assert_eq!(lines[5].chars().nth(19), Some('│'));
+        assert_eq!(lines[5].chars().nth(20), Some('│'));
+        assert!(lines[11].starts_with('└'));
+    }
+
+    #[test]
+    fn debug_flex_constraints() {
+        let child = EmptyLayout::new()
+            .intrinsic_size(IntrinsicSize::fill())
+            .with_label("Flex");
+        let mut layout = HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fill())
+            .add_child(child);
+        solve_layout(&mut layout, Size::new(400.0, 300.0));
+
+        let output = layout.debug_constraints();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1], " •Flex(size: 400x300, min: 0x0, max: 400x300)");
+    }
+}
@@ -22,6 +22,19 @@ pub enum LayoutError {
         id: GlobalId,
         axis: OverflowAxis,
     },
+    /// The layout tree has more nodes than the limit set in
+    /// [`SolveOptions::max_nodes`](crate::SolveOptions::max_nodes).
+    NodeLimitExceeded {
+        limit: usize,
+    },
+    /// The node's size is larger than the max constraints of its parent. This is only reported with the `constraint-checks` feature.
+    ConstraintViolation {
+        id: GlobalId,
+    },
+    /// The node has a fixed width that is larger than its max width.
+    ConflictingConstraints {
+        id: GlobalId,
+    },
 }
 
 impl LayoutError {
@@ -35,6 +48,18 @@ impl LayoutError {
     pub fn overflow(id: GlobalId, axis: OverflowAxis) -> Self {
         Self::Overflow { id, axis }
     }
+
+    pub fn node_limit_exceeded(limit: usize) -> Self {
+        Self::NodeLimitExceeded { limit }
+    }
+
+    pub fn constraint_violation(id: GlobalId) -> Self {
+        Self::ConstraintViolation { id }
+    }
+
+    pub fn conflicting_constraints(id: GlobalId) -> Self {
+        Self::ConflictingConstraints { id }
+    }
 }
 
 impl std::error::Error for LayoutError {}
@@ -52,6 +77,18 @@ impl std::fmt::Display for LayoutError {
             Self::Overflow { id, axis } => {
                 write!(f, "Widget(id:{id})'s children have overflown in the {axis}")
             }
+            Self::NodeLimitExceeded { limit } => {
+                write!(f, "The layout tree has more than {limit} nodes")
+            }
+            Self::ConstraintViolation { id } => {
+                write!(f, "Widget(id:{id}) is larger than its constraints")
+            }
+            Self::ConflictingConstraints { id } => {
+                write!(
+                    f,
+                    "Widget(id:{id}) has a fixed width larger than its max width"
+                )
+            }
         }
     }
 }
@@ -1,7 +1,9 @@
-use crate::constraints::impl_constraints;
+use super::{apply_min_size, flex_size, overflows, position_child};
+use crate::constraints::{ComputedSize, impl_constraints};
 use crate::{
-    AxisAlignment, BoxConstraints, BoxSizing, EmptyLayout, GlobalId, IntrinsicSize, Layout,
-    LayoutError, LayoutIter, Padding, Position, Size,
+    Alignment2D, AxisAlignment, Border, Bounds, BoxConstraints, BoxSizing, EmptyLayout, GlobalId,
+    IntrinsicSize, Layout, LayoutError, LayoutIter, LazyLayoutIter, Padding, Position,
+    RelativePadding, Size, error::OverflowAxis,
 };
 
 /// A [`Layout`] that only has one child node.
@@ -29,13 +31,36 @@ pub struct BlockLayout {
     pub(crate) size: Size,
     position: Position,
     padding: Padding,
+    /// The padding with percentages, which is resolved into `padding`.
+    relative_padding: Option<RelativePadding>,
+    border: Border,
     intrinsic_size: IntrinsicSize,
+    auto: bool,
+    default_sizing: Option<BoxSizing>,
     constraints: BoxConstraints,
+    /// The max width set by the user, which is kept when the layout is reset.
+    configured_max_width: Option<f32>,
     main_axis_alignment: AxisAlignment,
     cross_axis_alignment: AxisAlignment,
     child: Box<dyn Layout>,
     errors: Vec<LayoutError>,
+    report_overflow: bool,
     label: Option<String>,
+    z_index: i32,
+    opacity: f32,
+    hidden: bool,
+    /// The width given by a width link, which replaces the intrinsic width.
+    pinned_width: Option<f32>,
+    collapsed: bool,
+    /// Whether the solved layout is kept after the first solve.
+    frozen: bool,
+    /// Whether a frozen layout has been solved.
+    solved: bool,
+    cross_self: Option<AxisAlignment>,
+    /// The smallest size of the axes that shrink.
+    min_size: Size,
+    computed_width: Option<ComputedSize>,
+    computed_height: Option<ComputedSize>,
 }
 
 impl Default for BlockLayout {
@@ -44,14 +69,31 @@ impl Default for BlockLayout {
             id: GlobalId::new(),
             size: Size::default(),
             padding: Padding::default(),
+            relative_padding: None,
+            border: Border::default(),
             position: Position::default(),
             intrinsic_size: IntrinsicSize::default(),
+            auto: false,
+            default_sizing: None,
             constraints: BoxConstraints::default(),
+            configured_max_width: None,
             main_axis_alignment: AxisAlignment::default(),
             cross_axis_alignment: AxisAlignment::default(),
             errors: vec![],
+            report_overflow: true,
             child: Box::new(EmptyLayout::default()),
             label: None,
+            z_index: 0,
+            opacity: 1.0,
+            hidden: false,
+            pinned_width: None,
+            collapsed: false,
+            frozen: false,
+            solved: false,
+            cross_self: None,
+            min_size: Size::default(),
+            computed_width: None,
+            computed_height: None,
         }
     }
 }
@@ -64,6 +106,22 @@ impl BlockLayout {
         }
     }
 
+    /// Creates a [`BlockLayout`] from a child that has already been boxed.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{BlockLayout, EmptyLayout, Layout};
+    ///
+    /// let child: Box<dyn Layout> = Box::new(EmptyLayout::new());
+    /// let block = BlockLayout::from_boxed(child);
+    /// ```
+    pub fn from_boxed(child: Box<dyn Layout>) -> Self {
+        Self {
+            child,
+            ..Default::default()
+        }
+    }
+
     pub fn child(&self) -> &dyn Layout {
         self.child.as_ref()
     }
@@ -78,9 +136,135 @@ impl BlockLayout {
         self
     }
 
+    /// Set the paint order of the node relative to its siblings in the
+    /// [flattened](Layout::flatten) tree, nodes with a higher `z_index`
+    /// are drawn later. This does not affect the layout.
+    pub fn z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+
+    /// Set the opacity of the node, which is clamped between `0.0` and `1.0`.
+    /// This is passed on to the [flattened](Layout::flatten) tree and does
+    /// not affect the layout.
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Hide the node and its children from the [flattened](Layout::flatten)
+    /// tree. Hidden nodes still take up space in the layout.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Collapse the node, so that it takes up no space in the layout and
+    /// the parent leaves out the spacing around it. Unlike
+    /// [`hidden`](Self::hidden), which only skips drawing the node, this
+    /// removes it from the layout. A collapsed subtree reports no errors.
+    pub fn collapsed(mut self, collapsed: bool) -> Self {
+        self.collapsed = collapsed;
+        self
+    }
+
+    /// Set whether [`Overflow`](LayoutError::Overflow) errors are reported
+    /// for this layout, which is `true` by default. The child is still
+    /// sized and positioned in the same way.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, BlockLayout, EmptyLayout, IntrinsicSize, Size};
+    ///
+    /// let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 100.0));
+    /// let mut layout = BlockLayout::new(child)
+    ///     .intrinsic_size(IntrinsicSize::fixed(50.0, 50.0))
+    ///     .report_overflow(false);
+    ///
+    /// let errors = solve_layout(&mut layout, Size::unit(500.0));
+    /// assert!(errors.is_empty());
+    /// ```
+    pub fn report_overflow(mut self, report_overflow: bool) -> Self {
+        self.report_overflow = report_overflow;
+        self
+    }
+
+    /// Keep the solved layout of this node and its children after the
+    /// first solve. Later solves skip the subtree and only move it to its
+    /// new position, and resetting the tree doesn't clear it.
+    pub fn frozen(mut self, frozen: bool) -> Self {
+        self.frozen = frozen;
+        self.solved = false;
+        self
+    }
+
+    /// Align this node on its parent's cross axis, overriding the parent's
+    /// cross axis alignment.
+    pub fn cross_self(mut self, alignment: AxisAlignment) -> Self {
+        self.cross_self = Some(alignment);
+        self
+    }
+
+    /// Set the smallest size that the layout shrinks to, this only applies
+    /// to the axes that [shrink](BoxSizing::Shrink).
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, BlockLayout, EmptyLayout, Layout, Size};
+    ///
+    /// let mut block = BlockLayout::new(EmptyLayout::new())
+    ///     .min_size(Size::new(0.0, 100.0));
+    ///
+    /// solve_layout(&mut block, Size::unit(500.0));
+    /// assert_eq!(block.size(), Size::new(0.0, 100.0));
+    /// ```
+    pub fn min_size(mut self, min_size: Size) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
     /// Set the [`Padding`].
-    pub fn padding(mut self, padding: Padding) -> Self {
-        self.padding = padding;
+    ///
+    /// The padding can also be a [`RelativePadding`], with sides that are
+    /// a percentage of the space the layout is given by its parent.
+    pub fn padding(mut self, padding: impl Into<RelativePadding>) -> Self {
+        let padding = padding.into();
+        self.padding = padding.resolve(Size::default());
+        self.relative_padding = padding.is_relative().then_some(padding);
+        self
+    }
+
+    /// Sets this layout's [`Border`], the content is inset by the border
+    /// in addition to the padding.
+    pub fn border(mut self, border: Border) -> Self {
+        self.border = border;
+        self
+    }
+
+    /// Compute the width from the space available to the layout, instead
+    /// of using the intrinsic width. The layout takes part in its parent's
+    /// flex space like [`BoxSizing::Flex(1)`](BoxSizing::Flex), and the
+    /// closure is given that share of the space.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, BlockLayout, EmptyLayout, Layout, Size};
+    ///
+    /// let mut block = BlockLayout::new(EmptyLayout::new())
+    ///     .computed_width(|available| available / 2.0 - 20.0);
+    ///
+    /// solve_layout(&mut block, Size::unit(800.0));
+    /// assert_eq!(block.size().width, 380.0);
+    /// ```
+    pub fn computed_width(mut self, f: impl Fn(f32) -> f32 + Send + Sync + 'static) -> Self {
+        self.computed_width = Some(ComputedSize::new(f));
+        self
+    }
+
+    /// Compute the height from the space available to the layout, in the
+    /// same way as [`computed_width`](BlockLayout::computed_width).
+    pub fn computed_height(mut self, f: impl Fn(f32) -> f32 + Send + Sync + 'static) -> Self {
+        self.computed_height = Some(ComputedSize::new(f));
         self
     }
 
@@ -96,9 +280,46 @@ impl BlockLayout {
         self
     }
 
+    /// Center the children on both the main and cross axis.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, EmptyLayout, BlockLayout, IntrinsicSize, Layout, Position, Size};
+    ///
+    /// let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+    /// let mut layout = BlockLayout::new(child)
+    ///     .intrinsic_size(IntrinsicSize::fixed(200.0, 200.0))
+    ///     .center();
+    ///
+    /// solve_layout(&mut layout, Size::unit(500.0));
+    /// assert_eq!(layout.children()[0].position(), Position::unit(75.0));
+    /// ```
+    pub fn center(mut self) -> Self {
+        self.main_axis_alignment = AxisAlignment::Center;
+        self.cross_axis_alignment = AxisAlignment::Center;
+        self
+    }
+
+    /// Set the alignment on both axes at once, the main axis is the
+    /// `x-axis` and the cross axis is the `y-axis`.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{Alignment2D, BlockLayout, EmptyLayout};
+    ///
+    /// let block = BlockLayout::new(EmptyLayout::new())
+    ///     .align(Alignment2D::TopRight);
+    /// ```
+    pub fn align(mut self, alignment: Alignment2D) -> Self {
+        let (main_axis_alignment, cross_axis_alignment) = alignment.axes();
+        self.main_axis_alignment = main_axis_alignment;
+        self.cross_axis_alignment = cross_axis_alignment;
+        self
+    }
+
     fn align_main_axis_start(&mut self) {
         let mut x_pos = self.position.x;
-        x_pos += self.padding.left;
+        x_pos += self.insets().left;
         self.child.set_x(x_pos);
     }
 
@@ -110,13 +331,14 @@ impl BlockLayout {
 
     fn align_main_axis_end(&mut self) {
         let mut x_pos = self.position.x + self.size.width;
-        x_pos -= self.padding.right;
+        x_pos -= self.insets().right;
+        x_pos -= self.child.size().width;
 
         self.child.set_x(x_pos);
     }
 
     fn align_cross_axis_start(&mut self) {
-        let y = self.position.y + self.padding.top;
+        let y = self.position.y + self.insets().top;
         self.child.set_y(y);
     }
 
@@ -126,8 +348,52 @@ impl BlockLayout {
     }
 
     fn align_cross_axis_end(&mut self) {
-        self.child
-            .set_y(self.position.y + self.size.height - self.padding.bottom);
+        let mut y_pos = self.position.y + self.size.height;
+        y_pos -= self.insets().bottom;
+        y_pos -= self.child.size().height;
+
+        self.child.set_y(y_pos);
+    }
+
+    /// Copies the configuration and state of the layout, with
+    /// a different `id` and child.
+    fn clone_with(&self, id: GlobalId, child: Box<dyn Layout>) -> Self {
+        Self {
+            id,
+            size: self.size,
+            position: self.position,
+            padding: self.padding,
+            relative_padding: self.relative_padding,
+            border: self.border,
+            intrinsic_size: self.intrinsic_size,
+            auto: self.auto,
+            default_sizing: self.default_sizing,
+            constraints: self.constraints,
+            configured_max_width: self.configured_max_width,
+            main_axis_alignment: self.main_axis_alignment,
+            cross_axis_alignment: self.cross_axis_alignment,
+            child,
+            errors: self.errors.clone(),
+            report_overflow: self.report_overflow,
+            label: self.label.clone(),
+            z_index: self.z_index,
+            opacity: self.opacity,
+            hidden: self.hidden,
+            pinned_width: self.pinned_width,
+            collapsed: self.collapsed,
+            frozen: self.frozen,
+            solved: self.solved,
+            cross_self: self.cross_self,
+            min_size: self.min_size,
+            computed_width: self.computed_width.clone(),
+            computed_height: self.computed_height.clone(),
+        }
+    }
+
+    /// The total space between the edges of the layout and its
+    /// content, which is the padding plus the border.
+    fn insets(&self) -> Padding {
+        self.padding.with_border(self.border)
     }
 
     impl_constraints!();
@@ -138,6 +404,10 @@ impl Layout for BlockLayout {
         self.label.clone().unwrap_or("BlockLayout".to_string())
     }
 
+    fn short_label(&self) -> String {
+        self.label.clone().unwrap_or("B".to_string())
+    }
+
     fn id(&self) -> GlobalId {
         self.id
     }
@@ -150,6 +420,10 @@ impl Layout for BlockLayout {
         self.position = position;
     }
 
+    fn set_size(&mut self, size: Size) {
+        self.size = size;
+    }
+
     fn set_x(&mut self, x: f32) {
         self.position.x = x;
     }
@@ -162,16 +436,66 @@ impl Layout for BlockLayout {
         self.position
     }
 
+    fn content_bounds(&self) -> Bounds {
+        self.bounds().inset_by(self.insets())
+    }
+
     fn children(&self) -> &[Box<dyn Layout>] {
         std::slice::from_ref(&self.child)
     }
 
+    fn children_mut(&mut self) -> &mut [Box<dyn Layout>] {
+        std::slice::from_mut(&mut self.child)
+    }
+
     fn constraints(&self) -> BoxConstraints {
         self.constraints
     }
 
     fn get_intrinsic_size(&self) -> IntrinsicSize {
-        self.intrinsic_size
+        if self.collapsed {
+            return IntrinsicSize::fixed(0.0, 0.0);
+        }
+        let mut intrinsic_size = match self.default_sizing {
+            Some(sizing) if self.auto => self.intrinsic_size.or_default(sizing),
+            _ => self.intrinsic_size,
+        };
+        // Computed sizes take part in the flex space of the parent
+        if self.computed_width.is_some() {
+            intrinsic_size.width = BoxSizing::Flex(1);
+        }
+        if self.computed_height.is_some() {
+            intrinsic_size.height = BoxSizing::Flex(1);
+        }
+        if let Some(width) = self.pinned_width {
+            intrinsic_size.width = BoxSizing::Fixed(width);
+        }
+        intrinsic_size
+    }
+
+    fn get_padding(&self) -> Padding {
+        self.padding
+    }
+
+    #[cfg(feature = "taffy-interop")]
+    fn to_taffy_style(&self) -> taffy::Style {
+        let base = crate::taffy_interop::base_style(
+            self.get_intrinsic_size(),
+            self.padding,
+            self.border,
+            self.cross_self,
+        );
+        crate::taffy_interop::flex_style(
+            base,
+            taffy::FlexDirection::Row,
+            0,
+            self.main_axis_alignment,
+            self.cross_axis_alignment,
+        )
+    }
+
+    fn get_border(&self) -> Border {
+        self.border
     }
 
     fn set_max_height(&mut self, height: f32) {
@@ -190,76 +514,231 @@ impl Layout for BlockLayout {
         self.constraints.min_width = width;
     }
 
+    fn pin_width(&mut self, width: Option<f32>) {
+        self.pinned_width = width;
+    }
+
+    fn set_default_sizing(&mut self, sizing: BoxSizing) {
+        self.default_sizing = Some(sizing);
+        self.child.set_default_sizing(sizing);
+    }
+
+    fn reset(&mut self) {
+        if self.frozen && self.solved {
+            return;
+        }
+
+        self.size = Size::default();
+        self.position = Position::default();
+        if let Some(padding) = self.relative_padding {
+            self.padding = padding.resolve(Size::default());
+        }
+        self.constraints = BoxConstraints {
+            max_width: self.configured_max_width,
+            ..Default::default()
+        };
+        self.errors.clear();
+        self.child.reset();
+    }
+
     fn collect_errors(&mut self) -> Vec<LayoutError> {
-        self.errors
-            .drain(..)
-            .chain(self.child.collect_errors())
-            .collect::<Vec<_>>()
+        let mut errors = vec![];
+        self.collect_errors_into(&mut errors);
+        errors
+    }
+
+    fn collect_errors_into(&mut self, errors: &mut Vec<LayoutError>) {
+        let start = errors.len();
+        errors.append(&mut self.errors);
+        self.child.collect_errors_into(errors);
+        // A collapsed subtree has no space to fit in
+        if self.collapsed {
+            errors.truncate(start);
+        }
+    }
+
+    fn get_z_index(&self) -> i32 {
+        self.z_index
+    }
+
+    fn get_opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    fn is_collapsed(&self) -> bool {
+        self.collapsed
+    }
+
+    fn get_cross_self(&self) -> Option<AxisAlignment> {
+        self.cross_self
+    }
+
+    fn clone_boxed(&self) -> Box<dyn Layout> {
+        Box::new(self.clone_with(GlobalId::new(), self.child.clone_boxed()))
+    }
+
+    fn simplify(&mut self) {
+        self.child.simplify();
+        let child = std::mem::replace(&mut self.child, Box::new(EmptyLayout::new()));
+        if let Some(child) = child.collapse(false) {
+            self.child = child;
+        }
+    }
+
+    fn collapse(self: Box<Self>, _removable: bool) -> Option<Box<dyn Layout>> {
+        // The block has the same size and position as its child
+        let child_flex = matches!(
+            self.child.get_intrinsic_size().width,
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_)
+        ) || matches!(
+            self.child.get_intrinsic_size().height,
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_)
+        );
+        let redundant = self.padding == Padding::default()
+            && !self.frozen
+            && self.relative_padding.is_none()
+            && self.border == Border::default()
+            && self.get_intrinsic_size() == IntrinsicSize::default()
+            && !self.auto
+            && self.configured_max_width.is_none()
+            && self.label.is_none()
+            && self.z_index == 0
+            && self.opacity == 1.0
+            && !self.hidden
+            && self.cross_self.is_none()
+            && self.child.get_cross_self().is_none()
+            && !child_flex;
+
+        if redundant {
+            return Some(self.child);
+        }
+        Some(self)
+    }
+
+    fn clone_with_ids(&self) -> Box<dyn Layout> {
+        Box::new(self.clone_with(self.id, self.child.clone_with_ids()))
     }
 
     fn iter(&self) -> LayoutIter<'_> {
         LayoutIter { stack: vec![self] }
     }
 
+    fn iter_lazy(&self) -> LazyLayoutIter<'_> {
+        LazyLayoutIter::new(self)
+    }
+
     fn solve_min_constraints(&mut self) -> (f32, f32) {
+        if self.frozen && self.solved {
+            return (self.constraints.min_width, self.constraints.min_height);
+        }
+
+        #[cfg(feature = "profiling")]
+        let _timer = crate::profiling::timer("BlockLayout", crate::profiling::Pass::MinConstraints);
+
         let (min_width, min_height) = self.child.solve_min_constraints();
 
         // Set our min constraints to child + padding if intrinsic size
         // is not fixed.
         // If intrinsic size is fixed then set min constraints to fixed
         // width and/or height.
-        match self.intrinsic_size.width {
-            BoxSizing::Flex(_) | BoxSizing::Shrink => {
-                self.constraints.min_width = self.padding.left + self.padding.right + min_width;
+        match self.get_intrinsic_size().width {
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_) | BoxSizing::Shrink => {
+                self.constraints.min_width = self.insets().horizontal_sum() + min_width;
             }
             BoxSizing::Fixed(width) => self.constraints.min_width = width,
         }
 
-        match self.intrinsic_size.height {
-            BoxSizing::Flex(_) | BoxSizing::Shrink => {
-                self.constraints.min_height = self.padding.top + self.padding.bottom + min_height;
+        match self.get_intrinsic_size().height {
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_) | BoxSizing::Shrink => {
+                self.constraints.min_height = self.insets().vertical_sum() + min_height;
             }
             BoxSizing::Fixed(height) => self.constraints.min_height = height,
         }
 
+        let intrinsic_size = self.get_intrinsic_size();
+        apply_min_size(&mut self.constraints, intrinsic_size, self.min_size);
         (self.constraints.min_width, self.constraints.min_height)
     }
 
     fn solve_max_constraints(&mut self, space: Size) {
+        if self.frozen && self.solved {
+            return;
+        }
+
+        #[cfg(feature = "profiling")]
+        let _timer = crate::profiling::timer("BlockLayout", crate::profiling::Pass::MaxConstraints);
+
         let mut available_space = space;
-        available_space.width -= self.padding.horizontal_sum();
-        available_space.height -= self.padding.vertical_sum();
+        if let Some(computed) = &self.computed_width {
+            let width = computed.resolve(self.constraints.max_width.unwrap_or_default());
+            self.constraints.max_width = Some(width);
+            available_space.width = width;
+        }
+        if let Some(computed) = &self.computed_height {
+            let height = computed.resolve(self.constraints.max_height);
+            self.constraints.max_height = height;
+            available_space.height = height;
+        }
+        if let Some(padding) = self.relative_padding {
+            self.padding = padding.resolve(self.available_space());
+        }
+        available_space.width -= self.insets().horizontal_sum();
+        available_space.height -= self.insets().vertical_sum();
 
-        // TODO: should layout set max constraints when shrink?
         match self.child.get_intrinsic_size().width {
-            BoxSizing::Flex(_) => {
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_) => {
                 if self.child.constraints().max_width.is_none() {
-                    self.child.set_max_width(available_space.width)
+                    let min_width = self.child.constraints().min_width;
+                    self.child
+                        .set_max_width(available_space.width.max(min_width))
                 }
             }
             BoxSizing::Fixed(width) => {
                 self.child.set_max_width(width);
             }
-            BoxSizing::Shrink => {}
+            BoxSizing::Shrink => {
+                if self.child.constraints().max_width.is_none() {
+                    let min_width = self.child.constraints().min_width;
+                    self.child.set_max_width(min_width);
+                }
+            }
         }
 
         match self.child.get_intrinsic_size().height {
-            BoxSizing::Flex(_) => {
-                self.child.set_max_height(available_space.height);
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_) => {
+                let min_height = self.child.constraints().min_height;
+                self.child
+                    .set_max_height(available_space.height.max(min_height));
             }
             BoxSizing::Fixed(height) => {
                 self.child.set_max_height(height);
             }
-            BoxSizing::Shrink => {}
+            BoxSizing::Shrink => {
+                let min_height = self.child.constraints().min_height;
+                self.child.set_max_height(min_height);
+            }
         }
 
         self.child.solve_max_constraints(available_space);
     }
 
     fn update_size(&mut self) {
-        match self.intrinsic_size.width {
-            BoxSizing::Flex(_) => {
-                self.size.width = self.constraints.max_width.unwrap_or_default();
+        if self.frozen && self.solved {
+            return;
+        }
+
+        self.solved = self.frozen;
+
+        match self.get_intrinsic_size().width {
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_) => {
+                self.size.width = flex_size(
+                    self.constraints.min_width,
+                    self.constraints.max_width.unwrap_or_default(),
+                );
             }
             BoxSizing::Shrink => {
                 self.size.width = self.constraints.min_width;
@@ -269,9 +748,10 @@ impl Layout for BlockLayout {
             }
         }
 
-        match self.intrinsic_size.height {
-            BoxSizing::Flex(_) => {
-                self.size.height = self.constraints.max_height;
+        match self.get_intrinsic_size().height {
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_) => {
+                self.size.height =
+                    flex_size(self.constraints.min_height, self.constraints.max_height);
             }
             BoxSizing::Shrink => {
                 self.size.height = self.constraints.min_height;
@@ -282,17 +762,46 @@ impl Layout for BlockLayout {
         }
 
         self.child.update_size();
+
+        if !self.report_overflow {
+            return;
+        }
+
+        let insets = self.insets();
+        let child_size = self.child.size();
+        let main_axis_error = LayoutError::overflow(self.id, OverflowAxis::MainAxis);
+        let cross_axis_error = LayoutError::overflow(self.id, OverflowAxis::CrossAxis);
+        let main_axis_extent = child_size.width + insets.horizontal_sum();
+        let cross_axis_extent = child_size.height + insets.vertical_sum();
+
+        // Prevent duplicate errors
+        if !self.errors.contains(&main_axis_error) && overflows(main_axis_extent, self.size.width) {
+            self.errors.push(main_axis_error);
+        }
+
+        if !self.errors.contains(&cross_axis_error)
+            && overflows(cross_axis_extent, self.size.height)
+        {
+            self.errors.push(cross_axis_error);
+        }
     }
 
     fn position_children(&mut self) {
+        #[cfg(feature = "profiling")]
+        let _timer = crate::profiling::timer("BlockLayout", crate::profiling::Pass::Position);
+
         match self.main_axis_alignment {
-            AxisAlignment::Start => self.align_main_axis_start(),
+            AxisAlignment::Start | AxisAlignment::Stretch => self.align_main_axis_start(),
             AxisAlignment::Center => self.align_main_axis_center(),
             AxisAlignment::End => self.align_main_axis_end(),
         }
 
-        match self.cross_axis_alignment {
-            AxisAlignment::Start => self.align_cross_axis_start(),
+        let cross_axis_alignment = self
+            .child
+            .get_cross_self()
+            .unwrap_or(self.cross_axis_alignment);
+        match cross_axis_alignment {
+            AxisAlignment::Start | AxisAlignment::Stretch => self.align_cross_axis_start(),
             AxisAlignment::Center => self.align_cross_axis_center(),
             AxisAlignment::End => self.align_cross_axis_end(),
         }
@@ -305,7 +814,8 @@ impl Layout for BlockLayout {
                 child_id: self.child.id().to_owned(),
             });
         }
-        self.child.position_children();
+
+        position_child(self.child.as_mut());
     }
 }
 
@@ -392,6 +902,22 @@ mod test {
         assert_eq!(layout.constraints.min_height, 20.0 + 93.0 + 53.0);
     }
 
+    #[test]
+    fn border_insets_child_with_padding() {
+        let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fill());
+        let mut root = BlockLayout::new(child)
+            .border(Border::all(2.0))
+            .padding(Padding::all(10.0))
+            .intrinsic_size(IntrinsicSize::fill());
+
+        solve_layout(&mut root, Size::unit(200.0));
+
+        assert_eq!(root.child.position(), Position::unit(12.0));
+        assert_eq!(root.child.size(), Size::unit(200.0 - 24.0));
+        assert_eq!(root.get_border(), Border::all(2.0));
+        assert_eq!(root.get_padding(), Padding::all(10.0));
+    }
+
     #[test]
     fn shrink_sizing() {
         let window = Size::new(800.0, 800.0);
@@ -0,0 +1,390 @@
+use crate::{
+    AxisAlignment, Border, Bounds, BoxConstraints, BoxSizing, GlobalId, IntrinsicSize, Layout,
+    LayoutError, LayoutIter, LazyLayoutIter, Padding, Position, Size,
+};
+
+/// Wraps a [`Layout`] and skips solving it again when its constraints
+/// haven't changed since the last solve, for nodes that are expensive to
+/// solve.
+///
+/// The wrapper is transparent, it has the same id, label, size, position
+/// and children as the node it wraps. Resetting the wrapper keeps the
+/// cached layout, so the tree can be reset and solved again as usual. Use
+/// [`invalidate`](CachedLayout::invalidate) after changing the wrapped
+/// node, for example through [`children_mut`](Layout::children_mut).
+///
+/// # Example
+/// ```
+/// use cascada::{solve_layout, CachedLayout, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, Size};
+///
+/// let row = HorizontalLayout::new()
+///     .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0)));
+/// let mut layout = CachedLayout::new(row);
+///
+/// solve_layout(&mut layout, Size::unit(500.0));
+/// layout.reset();
+/// solve_layout(&mut layout, Size::unit(500.0));
+/// assert_eq!(layout.size(), Size::unit(50.0));
+/// ```
+#[derive(Debug)]
+pub struct CachedLayout {
+    inner: Box<dyn Layout>,
+    /// The constraints given by the parent.
+    constraints: BoxConstraints,
+    /// The min size of the wrapped node.
+    min_size: Option<(f32, f32)>,
+    /// The constraints that the wrapped node was last solved with.
+    solved_with: Option<BoxConstraints>,
+    /// The position that the children were last positioned at.
+    positioned_at: Option<Position>,
+    /// Whether the current solve is using the cached layout.
+    hit: bool,
+    errors: Vec<LayoutError>,
+}
+
+impl CachedLayout {
+    pub fn new(layout: impl Layout + 'static) -> Self {
+        Self::from_boxed(Box::new(layout))
+    }
+
+    pub fn from_boxed(layout: Box<dyn Layout>) -> Self {
+        Self {
+            inner: layout,
+            constraints: BoxConstraints::default(),
+            min_size: None,
+            solved_with: None,
+            positioned_at: None,
+            hit: false,
+            errors: vec![],
+        }
+    }
+
+    /// Get the wrapped layout.
+    pub fn inner(&self) -> &dyn Layout {
+        self.inner.as_ref()
+    }
+
+    /// Clear the cache, so the wrapped node is solved again the next time
+    /// the layout is solved.
+    pub fn invalidate(&mut self) {
+        self.min_size = None;
+        self.solved_with = None;
+        self.positioned_at = None;
+        self.hit = false;
+        self.errors.clear();
+        self.inner.reset();
+    }
+}
+
+impl Layout for CachedLayout {
+    fn label(&self) -> String {
+        self.inner.label()
+    }
+
+    fn short_label(&self) -> String {
+        self.inner.short_label()
+    }
+
+    fn solve_min_constraints(&mut self) -> (f32, f32) {
+        let (min_width, min_height) = match self.min_size {
+            Some(min_size) => min_size,
+            None => {
+                let min_size = self.inner.solve_min_constraints();
+                self.min_size = Some(min_size);
+                min_size
+            }
+        };
+        self.constraints.min_width = min_width;
+        self.constraints.min_height = min_height;
+        (min_width, min_height)
+    }
+
+    fn solve_max_constraints(&mut self, space: Size) {
+        self.hit = self.solved_with == Some(self.constraints);
+        if self.hit {
+            return;
+        }
+
+        // Clear the state from the previous solve
+        if self.solved_with.is_some() {
+            self.inner.reset();
+            self.inner.solve_min_constraints();
+        }
+
+        if let Some(max_width) = self.constraints.max_width {
+            self.inner.set_max_width(max_width);
+        }
+        self.inner.set_max_height(self.constraints.max_height);
+        self.inner.set_min_width(self.constraints.min_width);
+        self.inner.set_min_height(self.constraints.min_height);
+        self.inner.solve_max_constraints(space);
+        self.solved_with = Some(self.constraints);
+        self.positioned_at = None;
+    }
+
+    fn position_children(&mut self) {
+        let position = self.inner.position();
+        if self.hit && self.positioned_at == Some(position) {
+            return;
+        }
+
+        self.inner.position_children();
+        self.positioned_at = Some(position);
+    }
+
+    fn update_size(&mut self) {
+        if !self.hit {
+            self.inner.update_size();
+        }
+    }
+
+    /// Resets the constraints given by the parent, the cached layout
+    /// is kept.
+    fn reset(&mut self) {
+        self.constraints = BoxConstraints::default();
+        self.hit = false;
+    }
+
+    fn collect_errors(&mut self) -> Vec<LayoutError> {
+        if !self.hit {
+            self.errors = self.inner.collect_errors();
+        }
+        self.errors.clone()
+    }
+
+    fn collect_errors_into(&mut self, errors: &mut Vec<LayoutError>) {
+        if !self.hit {
+            self.errors.clear();
+            self.inner.collect_errors_into(&mut self.errors);
+        }
+        errors.extend_from_slice(&self.errors);
+    }
+
+    fn id(&self) -> GlobalId {
+        self.inner.id()
+    }
+
+    fn constraints(&self) -> BoxConstraints {
+        self.constraints
+    }
+
+    fn get_intrinsic_size(&self) -> IntrinsicSize {
+        self.inner.get_intrinsic_size()
+    }
+
+    fn get_padding(&self) -> Padding {
+        self.inner.get_padding()
+    }
+
+    fn get_border(&self) -> Border {
+        self.inner.get_border()
+    }
+
+    fn get_z_index(&self) -> i32 {
+        self.inner.get_z_index()
+    }
+
+    fn get_opacity(&self) -> f32 {
+        self.inner.get_opacity()
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.inner.is_hidden()
+    }
+
+    fn is_collapsed(&self) -> bool {
+        self.inner.is_collapsed()
+    }
+
+    fn get_cross_self(&self) -> Option<AxisAlignment> {
+        self.inner.get_cross_self()
+    }
+
+    fn size(&self) -> Size {
+        self.inner.size()
+    }
+
+    fn position(&self) -> Position {
+        self.inner.position()
+    }
+
+    fn content_bounds(&self) -> Bounds {
+        self.inner.content_bounds()
+    }
+
+    fn children(&self) -> &[Box<dyn Layout>] {
+        self.inner.children()
+    }
+
+    fn children_mut(&mut self) -> &mut [Box<dyn Layout>] {
+        self.inner.children_mut()
+    }
+
+    fn set_max_width(&mut self, width: f32) {
+        self.constraints.max_width = Some(width);
+    }
+
+    fn set_max_height(&mut self, height: f32) {
+        self.constraints.max_height = height;
+    }
+
+    fn set_min_width(&mut self, width: f32) {
+        self.constraints.min_width = width;
+    }
+
+    fn set_min_height(&mut self, height: f32) {
+        self.constraints.min_height = height;
+    }
+
+    fn needs_width_for_height(&self) -> bool {
+        self.inner.needs_width_for_height()
+    }
+
+    fn measure_height(&mut self, measure: bool) {
+        if self.inner.needs_width_for_height() {
+            self.inner.measure_height(measure);
+            self.invalidate();
+        }
+    }
+
+    fn pin_width(&mut self, width: Option<f32>) {
+        let intrinsic_size = self.inner.get_intrinsic_size();
+        self.inner.pin_width(width);
+        if self.inner.get_intrinsic_size() != intrinsic_size {
+            self.invalidate();
+        }
+    }
+
+    fn set_default_sizing(&mut self, sizing: BoxSizing) {
+        self.inner.set_default_sizing(sizing);
+        self.invalidate();
+    }
+
+    fn set_size(&mut self, size: Size) {
+        self.inner.set_size(size);
+    }
+
+    fn set_x(&mut self, x: f32) {
+        self.inner.set_x(x);
+    }
+
+    fn set_y(&mut self, y: f32) {
+        self.inner.set_y(y);
+    }
+
+    fn simplify(&mut self) {
+        self.inner.simplify();
+        self.invalidate();
+    }
+
+    fn collapse(self: Box<Self>, _removable: bool) -> Option<Box<dyn Layout>> {
+        Some(self)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn Layout> {
+        Box::new(Self::from_boxed(self.inner.clone_boxed()))
+    }
+
+    fn clone_with_ids(&self) -> Box<dyn Layout> {
+        Box::new(Self::from_boxed(self.inner.clone_with_ids()))
+    }
+
+    fn iter(&self) -> LayoutIter<'_> {
+        LayoutIter { stack: vec![self] }
+    }
+
+    fn iter_lazy(&self) -> LazyLayoutIter<'_> {
+        LazyLayoutIter::new(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        CustomLayout, EmptyLayout, HorizontalLayout, solve_layout, solve_layout_with_insets,
+    };
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// A node that counts how many times it has been sized.
+    fn counted(count: Arc<AtomicU32>) -> CustomLayout {
+        CustomLayout::new()
+            .intrinsic_size(IntrinsicSize::fill())
+            .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()))
+            .on_size(move |constraints| {
+                count.fetch_add(1, Ordering::Relaxed);
+                Size::new(
+                    constraints.max_width.unwrap_or_default(),
+                    constraints.max_height,
+                )
+            })
+    }
+
+    #[test]
+    fn solve_once_with_same_constraints() {
+        let count = Arc::new(AtomicU32::new(0));
+        let mut root = HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fill())
+            .add_child(CachedLayout::new(counted(count.clone())));
+
+        solve_layout(&mut root, Size::unit(500.0));
+        root.reset();
+        solve_layout(&mut root, Size::unit(500.0));
+
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        assert_eq!(root.children()[0].size(), Size::unit(500.0));
+        assert_eq!(root.children()[0].children()[0].size(), Size::unit(500.0));
+    }
+
+    #[test]
+    fn solve_again_with_new_constraints() {
+        let count = Arc::new(AtomicU32::new(0));
+        let mut root = HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fill())
+            .add_child(CachedLayout::new(counted(count.clone())));
+
+        solve_layout(&mut root, Size::unit(500.0));
+        root.reset();
+        solve_layout(&mut root, Size::unit(400.0));
+
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+        assert_eq!(root.children()[0].size(), Size::unit(400.0));
+    }
+
+    #[test]
+    fn reposition_cached_layout() {
+        let count = Arc::new(AtomicU32::new(0));
+        let mut root = HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fixed(300.0, 300.0))
+            .add_child(CachedLayout::new(counted(count.clone())));
+
+        solve_layout(&mut root, Size::unit(500.0));
+        root.reset();
+        let insets = Padding::new(20.0, 0.0, 10.0, 0.0);
+        solve_layout_with_insets(&mut root, Size::unit(500.0), insets);
+
+        let cached = &root.children()[0];
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        assert_eq!(cached.position(), Position::new(20.0, 10.0));
+        assert_eq!(cached.children()[0].position(), Position::new(20.0, 10.0));
+    }
+
+    #[test]
+    fn report_cached_errors() {
+        let inner = HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fixed(50.0, 50.0))
+            .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 20.0)));
+        let mut root = HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fill())
+            .add_child(CachedLayout::new(inner));
+
+        let first = solve_layout(&mut root, Size::unit(500.0));
+        root.reset();
+        let second = solve_layout(&mut root, Size::unit(500.0));
+
+        let overflow = |error: &LayoutError| matches!(error, LayoutError::Overflow { .. });
+        assert!(first.iter().any(overflow));
+        assert_eq!(first, second);
+    }
+}
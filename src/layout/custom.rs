@@ -0,0 +1,666 @@
+use super::{flex_size, position_child};
+use crate::constraints::impl_constraints;
+use crate::{
+    AxisAlignment, BoxConstraints, BoxSizing, GlobalId, IntrinsicSize, Layout, LayoutError,
+    LayoutIter, LazyLayoutIter, Position, Size,
+};
+use std::sync::Arc;
+
+type MinSizeFn = dyn Fn(&[Box<dyn Layout>]) -> Size + Send + Sync;
+type MaxConstraintsFn = dyn Fn(BoxConstraints, &mut [Box<dyn Layout>]) + Send + Sync;
+type SizeFn = dyn Fn(BoxConstraints) -> Size + Send + Sync;
+type PositionFn = dyn Fn(Position, Size, &mut [Box<dyn Layout>]) + Send + Sync;
+type HeightForWidthFn = dyn Fn(f32) -> f32 + Send + Sync;
+
+/// A [`Layout`] whose solve passes are provided as closures, for node
+/// types that aren't built into the crate.
+///
+/// The solver still drives the traversal of the tree, each closure only
+/// handles this node and the children are solved after, or before in the
+/// case of the min constraints, the closure runs. Every pass has a
+/// default, so only the passes that differ need to be provided:
+///
+/// - [`on_min_size`](CustomLayout::on_min_size) is given the children
+///   after their min constraints are solved and returns the minimum size.
+///   By default this is zero, or the fixed size.
+/// - [`on_max_constraints`](CustomLayout::on_max_constraints) is given the
+///   constraints of this node and sets the max constraints of the
+///   children. By default each child is given the max size of this node.
+/// - [`on_size`](CustomLayout::on_size) returns the final size from the
+///   constraints. By default the node is sized by its [`IntrinsicSize`].
+/// - [`on_position`](CustomLayout::on_position) is given the position and
+///   size of this node and positions the children. By default every child
+///   is placed at the position of this node.
+/// - [`on_height_for_width`](CustomLayout::on_height_for_width) is given
+///   the solved width and returns the min height, for content that wraps.
+///   By default the height doesn't depend on the width.
+///
+/// # Example
+/// ```
+/// use cascada::{solve_layout, CustomLayout, HorizontalLayout, Layout, Size};
+///
+/// let glyph = || CustomLayout::new().on_min_size(|_| Size::new(8.0, 16.0));
+/// let mut row = HorizontalLayout::new().add_children([glyph(), glyph()]);
+///
+/// solve_layout(&mut row, Size::unit(500.0));
+/// assert_eq!(row.size(), Size::new(16.0, 16.0));
+/// ```
+pub struct CustomLayout {
+    id: GlobalId,
+    size: Size,
+    position: Position,
+    intrinsic_size: IntrinsicSize,
+    auto: bool,
+    default_sizing: Option<BoxSizing>,
+    constraints: BoxConstraints,
+    /// The max width set by the user, which is kept when the layout is reset.
+    configured_max_width: Option<f32>,
+    children: Vec<Box<dyn Layout>>,
+    errors: Vec<LayoutError>,
+    label: Option<String>,
+    z_index: i32,
+    opacity: f32,
+    hidden: bool,
+    /// The width given by a width link, which replaces the intrinsic width.
+    pinned_width: Option<f32>,
+    /// Whether the solved layout is kept after the first solve.
+    frozen: bool,
+    /// Whether a frozen layout has been solved.
+    solved: bool,
+    cross_self: Option<AxisAlignment>,
+    min_size: Option<Arc<MinSizeFn>>,
+    max_constraints: Option<Arc<MaxConstraintsFn>>,
+    size_fn: Option<Arc<SizeFn>>,
+    position_fn: Option<Arc<PositionFn>>,
+    height_for_width: Option<Arc<HeightForWidthFn>>,
+    /// The height measured from the solved width.
+    measured_height: Option<f32>,
+}
+
+impl Default for CustomLayout {
+    fn default() -> Self {
+        Self {
+            id: GlobalId::new(),
+            size: Size::default(),
+            position: Position::default(),
+            intrinsic_size: IntrinsicSize::default(),
+            auto: false,
+            default_sizing: None,
+            constraints: BoxConstraints::default(),
+            configured_max_width: None,
+            children: vec![],
+            errors: vec![],
+            label: None,
+            z_index: 0,
+            opacity: 1.0,
+            hidden: false,
+            pinned_width: None,
+            frozen: false,
+            solved: false,
+            cross_self: None,
+            min_size: None,
+            max_constraints: None,
+            size_fn: None,
+            position_fn: None,
+            height_for_width: None,
+            measured_height: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for CustomLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomLayout")
+            .field("id", &self.id)
+            .field("size", &self.size)
+            .field("position", &self.position)
+            .field("intrinsic_size", &self.intrinsic_size)
+            .field("constraints", &self.constraints)
+            .field("children", &self.children)
+            .field("label", &self.label)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CustomLayout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_id(mut self, id: GlobalId) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// Set the paint order of the node relative to its siblings in the
+    /// [flattened](Layout::flatten) tree, nodes with a higher `z_index`
+    /// are drawn later. This does not affect the layout.
+    pub fn z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+
+    /// Set the opacity of the node, which is clamped between `0.0` and `1.0`.
+    /// This is passed on to the [flattened](Layout::flatten) tree and does
+    /// not affect the layout.
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Hide the node and its children from the [flattened](Layout::flatten)
+    /// tree. Hidden nodes still take up space in the layout.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Keep the solved layout of this node and its children after the
+    /// first solve. Later solves skip the subtree and only move it to its
+    /// new position, and resetting the tree doesn't clear it.
+    pub fn frozen(mut self, frozen: bool) -> Self {
+        self.frozen = frozen;
+        self.solved = false;
+        self
+    }
+
+    /// Align this node on its parent's cross axis, overriding the parent's
+    /// cross axis alignment.
+    pub fn cross_self(mut self, alignment: AxisAlignment) -> Self {
+        self.cross_self = Some(alignment);
+        self
+    }
+
+    /// Add a child node.
+    pub fn add_child(mut self, child: impl Layout + 'static) -> Self {
+        self.children.push(Box::new(child));
+        self
+    }
+
+    /// Add a boxed child node.
+    pub fn add_boxed_child(mut self, child: Box<dyn Layout>) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Compute the minimum size of the node from its children, whose min
+    /// constraints have already been solved. A fixed intrinsic size takes
+    /// priority over the returned size.
+    pub fn on_min_size(
+        mut self,
+        f: impl Fn(&[Box<dyn Layout>]) -> Size + Send + Sync + 'static,
+    ) -> Self {
+        self.min_size = Some(Arc::new(f));
+        self
+    }
+
+    /// Set the max constraints of the children from the constraints of
+    /// this node. The max constraints of the children are solved after.
+    pub fn on_max_constraints(
+        mut self,
+        f: impl Fn(BoxConstraints, &mut [Box<dyn Layout>]) + Send + Sync + 'static,
+    ) -> Self {
+        self.max_constraints = Some(Arc::new(f));
+        self
+    }
+
+    /// Compute the final size of the node from its constraints.
+    pub fn on_size(mut self, f: impl Fn(BoxConstraints) -> Size + Send + Sync + 'static) -> Self {
+        self.size_fn = Some(Arc::new(f));
+        self
+    }
+
+    /// Position the children, given the position and size of this node.
+    /// The children of each child are positioned after.
+    pub fn on_position(
+        mut self,
+        f: impl Fn(Position, Size, &mut [Box<dyn Layout>]) + Send + Sync + 'static,
+    ) -> Self {
+        self.position_fn = Some(Arc::new(f));
+        self
+    }
+
+    /// Compute the min height of the node from its solved width, for
+    /// content such as wrapping text whose height depends on its width.
+    /// The min height is first solved without it, then the tree is solved
+    /// again once the width is known, so the parents fit the new height.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, BoxSizing, CustomLayout, IntrinsicSize, Layout, Size, VerticalLayout};
+    ///
+    /// // 500 pixels of text in lines that are 20 pixels tall
+    /// let text = CustomLayout::new()
+    ///     .intrinsic_size(IntrinsicSize {
+    ///         width: BoxSizing::Flex(1),
+    ///         height: BoxSizing::Shrink,
+    ///     })
+    ///     .on_height_for_width(|width| (500.0 / width).ceil() * 20.0);
+    /// let mut column = VerticalLayout::new()
+    ///     .intrinsic_size(IntrinsicSize::fixed(200.0, 400.0))
+    ///     .add_child(text);
+    ///
+    /// solve_layout(&mut column, Size::unit(1000.0));
+    /// assert_eq!(column.children()[0].size(), Size::new(200.0, 60.0));
+    /// ```
+    pub fn on_height_for_width(mut self, f: impl Fn(f32) -> f32 + Send + Sync + 'static) -> Self {
+        self.height_for_width = Some(Arc::new(f));
+        self
+    }
+
+    /// Copies the configuration and state of the layout, with
+    /// a different `id` and children.
+    fn clone_with(&self, id: GlobalId, children: Vec<Box<dyn Layout>>) -> Self {
+        Self {
+            id,
+            size: self.size,
+            position: self.position,
+            intrinsic_size: self.intrinsic_size,
+            auto: self.auto,
+            default_sizing: self.default_sizing,
+            constraints: self.constraints,
+            configured_max_width: self.configured_max_width,
+            children,
+            errors: self.errors.clone(),
+            label: self.label.clone(),
+            z_index: self.z_index,
+            opacity: self.opacity,
+            hidden: self.hidden,
+            pinned_width: self.pinned_width,
+            frozen: self.frozen,
+            solved: self.solved,
+            cross_self: self.cross_self,
+            min_size: self.min_size.clone(),
+            max_constraints: self.max_constraints.clone(),
+            size_fn: self.size_fn.clone(),
+            position_fn: self.position_fn.clone(),
+            height_for_width: self.height_for_width.clone(),
+            measured_height: self.measured_height,
+        }
+    }
+
+    impl_constraints!();
+}
+
+impl Layout for CustomLayout {
+    fn label(&self) -> String {
+        self.label.clone().unwrap_or("CustomLayout".to_string())
+    }
+
+    fn short_label(&self) -> String {
+        self.label.clone().unwrap_or("C".to_string())
+    }
+
+    fn solve_min_constraints(&mut self) -> (f32, f32) {
+        if self.frozen && self.solved {
+            return (self.constraints.min_width, self.constraints.min_height);
+        }
+
+        #[cfg(feature = "profiling")]
+        let _timer =
+            crate::profiling::timer("CustomLayout", crate::profiling::Pass::MinConstraints);
+
+        for child in &mut self.children {
+            child.solve_min_constraints();
+        }
+
+        let min_size = match &self.min_size {
+            Some(f) => f(&self.children),
+            None => Size::default(),
+        };
+        self.constraints.min_width = match self.get_intrinsic_size().width {
+            BoxSizing::Fixed(width) => width,
+            _ => min_size.width,
+        };
+        self.constraints.min_height = match self.get_intrinsic_size().height {
+            BoxSizing::Fixed(height) => height,
+            _ => min_size
+                .height
+                .max(self.measured_height.unwrap_or_default()),
+        };
+
+        (self.constraints.min_width, self.constraints.min_height)
+    }
+
+    fn solve_max_constraints(&mut self, _space: Size) {
+        if self.frozen && self.solved {
+            return;
+        }
+
+        #[cfg(feature = "profiling")]
+        let _timer =
+            crate::profiling::timer("CustomLayout", crate::profiling::Pass::MaxConstraints);
+
+        match &self.max_constraints {
+            Some(f) => f(self.constraints, &mut self.children),
+            None => {
+                for child in &mut self.children {
+                    if child.constraints().max_width.is_none() {
+                        child.set_max_width(self.constraints.max_width.unwrap_or_default());
+                    }
+                    child.set_max_height(self.constraints.max_height);
+                }
+            }
+        }
+
+        for child in &mut self.children {
+            let space = Size::new(
+                child.constraints().max_width.unwrap_or_default(),
+                child.constraints().max_height,
+            );
+            child.solve_max_constraints(space);
+        }
+    }
+
+    fn position_children(&mut self) {
+        #[cfg(feature = "profiling")]
+        let _timer = crate::profiling::timer("CustomLayout", crate::profiling::Pass::Position);
+
+        match &self.position_fn {
+            Some(f) => f(self.position, self.size, &mut self.children),
+            None => {
+                for child in &mut self.children {
+                    child.set_position(self.position);
+                }
+            }
+        }
+
+        for child in &mut self.children {
+            position_child(child.as_mut());
+        }
+    }
+
+    fn update_size(&mut self) {
+        if self.frozen && self.solved {
+            return;
+        }
+
+        self.solved = self.frozen;
+
+        if let Some(f) = &self.size_fn {
+            self.size = f(self.constraints);
+        } else {
+            self.size.width = match self.get_intrinsic_size().width {
+                BoxSizing::Flex(_) | BoxSizing::FlexF(_) => flex_size(
+                    self.constraints.min_width,
+                    self.constraints.max_width.unwrap_or_default(),
+                ),
+                BoxSizing::Shrink => self.constraints.min_width,
+                BoxSizing::Fixed(width) => width,
+            };
+            self.size.height = match self.get_intrinsic_size().height {
+                BoxSizing::Flex(_) | BoxSizing::FlexF(_) => {
+                    flex_size(self.constraints.min_height, self.constraints.max_height)
+                }
+                BoxSizing::Shrink => self.constraints.min_height,
+                BoxSizing::Fixed(height) => height,
+            };
+        }
+
+        for child in &mut self.children {
+            child.update_size();
+        }
+    }
+
+    fn reset(&mut self) {
+        if self.frozen && self.solved {
+            return;
+        }
+
+        self.size = Size::default();
+        self.position = Position::default();
+        self.constraints = BoxConstraints {
+            max_width: self.configured_max_width,
+            ..Default::default()
+        };
+        self.errors.clear();
+        for child in &mut self.children {
+            child.reset();
+        }
+    }
+
+    fn collect_errors(&mut self) -> Vec<LayoutError> {
+        self.errors
+            .drain(..)
+            .chain(
+                self.children
+                    .iter_mut()
+                    .flat_map(|child| child.collect_errors()),
+            )
+            .collect::<Vec<_>>()
+    }
+
+    fn collect_errors_into(&mut self, errors: &mut Vec<LayoutError>) {
+        errors.append(&mut self.errors);
+        for child in &mut self.children {
+            child.collect_errors_into(errors);
+        }
+    }
+
+    fn id(&self) -> GlobalId {
+        self.id
+    }
+
+    fn constraints(&self) -> BoxConstraints {
+        self.constraints
+    }
+
+    fn get_intrinsic_size(&self) -> IntrinsicSize {
+        let mut intrinsic_size = match self.default_sizing {
+            Some(sizing) if self.auto => self.intrinsic_size.or_default(sizing),
+            _ => self.intrinsic_size,
+        };
+        if let Some(width) = self.pinned_width {
+            intrinsic_size.width = BoxSizing::Fixed(width);
+        }
+        intrinsic_size
+    }
+
+    fn get_z_index(&self) -> i32 {
+        self.z_index
+    }
+
+    fn get_opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    fn get_cross_self(&self) -> Option<AxisAlignment> {
+        self.cross_self
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn position(&self) -> Position {
+        self.position
+    }
+
+    fn children(&self) -> &[Box<dyn Layout>] {
+        &self.children
+    }
+
+    fn children_mut(&mut self) -> &mut [Box<dyn Layout>] {
+        &mut self.children
+    }
+
+    fn set_max_width(&mut self, width: f32) {
+        self.constraints.max_width = Some(width);
+    }
+
+    fn set_max_height(&mut self, height: f32) {
+        self.constraints.max_height = height;
+    }
+
+    fn set_min_width(&mut self, width: f32) {
+        self.constraints.min_width = width;
+    }
+
+    fn set_min_height(&mut self, height: f32) {
+        self.constraints.min_height = height;
+    }
+
+    fn needs_width_for_height(&self) -> bool {
+        self.height_for_width.is_some()
+    }
+
+    fn measure_height(&mut self, measure: bool) {
+        self.measured_height = self
+            .height_for_width
+            .as_ref()
+            .filter(|_| measure)
+            .map(|f| f(self.size.width));
+    }
+
+    fn pin_width(&mut self, width: Option<f32>) {
+        self.pinned_width = width;
+    }
+
+    fn set_default_sizing(&mut self, sizing: BoxSizing) {
+        self.default_sizing = Some(sizing);
+        for child in &mut self.children {
+            child.set_default_sizing(sizing);
+        }
+    }
+
+    fn set_size(&mut self, size: Size) {
+        self.size = size;
+    }
+
+    fn set_x(&mut self, x: f32) {
+        self.position.x = x;
+    }
+
+    fn set_y(&mut self, y: f32) {
+        self.position.y = y;
+    }
+
+    fn simplify(&mut self) {
+        for child in &mut self.children {
+            child.simplify();
+        }
+    }
+
+    // The closures may depend on any of the children
+    fn collapse(self: Box<Self>, _removable: bool) -> Option<Box<dyn Layout>> {
+        Some(self)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn Layout> {
+        let children = self
+            .children
+            .iter()
+            .map(|child| child.clone_boxed())
+            .collect();
+        Box::new(self.clone_with(GlobalId::new(), children))
+    }
+
+    fn clone_with_ids(&self) -> Box<dyn Layout> {
+        let children = self
+            .children
+            .iter()
+            .map(|child| child.clone_with_ids())
+            .collect();
+        Box::new(self.clone_with(self.id, children))
+    }
+
+    fn iter(&self) -> LayoutIter<'_> {
+        LayoutIter { stack: vec![self] }
+    }
+
+    fn iter_lazy(&self) -> LazyLayoutIter<'_> {
+        LazyLayoutIter::new(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{EmptyLayout, HorizontalLayout, VerticalLayout, solve_layout};
+
+    #[test]
+    fn constant_size() {
+        let custom = CustomLayout::new().on_size(|_| Size::new(42.0, 24.0));
+        let mut root = HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fill())
+            .add_child(custom.on_min_size(|_| Size::new(42.0, 24.0)))
+            .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()));
+
+        let errors = solve_layout(&mut root, Size::unit(500.0));
+
+        assert!(errors.is_empty());
+        assert_eq!(root.children()[0].size(), Size::new(42.0, 24.0));
+        assert_eq!(root.children()[1].position().x, 42.0);
+        assert_eq!(root.children()[1].size().width, 500.0 - 42.0);
+    }
+
+    #[test]
+    fn position_children_with_closure() {
+        let child = || EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(10.0, 10.0));
+        // Stacks the children diagonally
+        let mut root = CustomLayout::new()
+            .add_child(child())
+            .add_child(child())
+            .on_min_size(|children| {
+                let mut size = Size::default();
+                for child in children {
+                    size.width += child.constraints().min_width;
+                    size.height += child.constraints().min_height;
+                }
+                size
+            })
+            .on_position(|position, _, children| {
+                let mut position = position;
+                for child in children {
+                    child.set_position(position);
+                    position.x += child.size().width;
+                    position.y += child.size().height;
+                }
+            });
+
+        solve_layout(&mut root, Size::unit(500.0));
+
+        assert_eq!(root.size(), Size::unit(20.0));
+        assert_eq!(root.children()[1].position(), Position::unit(10.0));
+    }
+
+    #[test]
+    fn shrink_parent_fits_height_for_width() {
+        // 500 pixels of text in lines that are 20 pixels tall
+        let text = CustomLayout::new()
+            .intrinsic_size(IntrinsicSize {
+                width: BoxSizing::Flex(1),
+                height: BoxSizing::Shrink,
+            })
+            .on_height_for_width(|width| (500.0 / width).ceil() * 20.0);
+        let mut root = VerticalLayout::new()
+            .intrinsic_size(IntrinsicSize {
+                width: BoxSizing::Flex(1),
+                height: BoxSizing::Shrink,
+            })
+            .add_child(text)
+            .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0)));
+
+        let errors = solve_layout(&mut root, Size::new(200.0, 1000.0));
+
+        assert!(errors.is_empty());
+        assert_eq!(root.children()[0].size(), Size::new(200.0, 60.0));
+        assert_eq!(root.children()[1].position().y, 60.0);
+        assert_eq!(root.size().height, 110.0);
+
+        // A wider window gives fewer lines
+        root.reset();
+        solve_layout(&mut root, Size::new(500.0, 1000.0));
+        assert_eq!(root.children()[0].size().height, 20.0);
+        assert_eq!(root.size().height, 70.0);
+    }
+}
@@ -1,18 +1,68 @@
+use super::{apply_min_size, flex_size, is_zero_sized};
 use crate::constraints::impl_constraints;
 use crate::{
-    BoxConstraints, BoxSizing, GlobalId, IntrinsicSize, Layout, LayoutIter, Position, Size,
+    AxisAlignment, BoxConstraints, BoxSizing, GlobalId, IntrinsicSize, Layout, LayoutIter,
+    LazyLayoutIter, Position, Size,
 };
 
 /// An empty [`Layout`] with no child notes.
-#[derive(Debug, Default, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct EmptyLayout {
     id: GlobalId,
     pub(crate) size: Size,
     position: Position,
     intrinsic_size: IntrinsicSize,
+    auto: bool,
+    default_sizing: Option<BoxSizing>,
     constraints: BoxConstraints,
+    /// The max width set by the user, which is kept when the layout is reset.
+    configured_max_width: Option<f32>,
     errors: Vec<crate::LayoutError>,
     label: Option<String>,
+    z_index: i32,
+    opacity: f32,
+    hidden: bool,
+    /// The width given by a width link, which replaces the intrinsic width.
+    pinned_width: Option<f32>,
+    collapsed: bool,
+    /// Whether the solved layout is kept after the first solve.
+    frozen: bool,
+    /// Whether a frozen layout has been solved.
+    solved: bool,
+    cross_self: Option<AxisAlignment>,
+    /// The smallest size of the axes that shrink.
+    min_size: Size,
+    min_touch_target: Option<Size>,
+    /// The position and size before growing to the touch target.
+    unexpanded: Option<(Position, Size)>,
+}
+
+impl Default for EmptyLayout {
+    fn default() -> Self {
+        Self {
+            id: GlobalId::new(),
+            size: Size::default(),
+            position: Position::default(),
+            intrinsic_size: IntrinsicSize::default(),
+            auto: false,
+            default_sizing: None,
+            constraints: BoxConstraints::default(),
+            configured_max_width: None,
+            errors: vec![],
+            label: None,
+            z_index: 0,
+            opacity: 1.0,
+            hidden: false,
+            pinned_width: None,
+            collapsed: false,
+            frozen: false,
+            solved: false,
+            cross_self: None,
+            min_size: Size::default(),
+            min_touch_target: None,
+            unexpanded: None,
+        }
+    }
 }
 
 impl EmptyLayout {
@@ -30,6 +80,85 @@ impl EmptyLayout {
         self
     }
 
+    /// Set the paint order of the node relative to its siblings in the
+    /// [flattened](Layout::flatten) tree, nodes with a higher `z_index`
+    /// are drawn later. This does not affect the layout.
+    pub fn z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+
+    /// Set the opacity of the node, which is clamped between `0.0` and `1.0`.
+    /// This is passed on to the [flattened](Layout::flatten) tree and does
+    /// not affect the layout.
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Hide the node and its children from the [flattened](Layout::flatten)
+    /// tree. Hidden nodes still take up space in the layout.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Collapse the node, so that it takes up no space in the layout and
+    /// the parent leaves out the spacing around it. Unlike
+    /// [`hidden`](Self::hidden), which only skips drawing the node, this
+    /// removes it from the layout. A collapsed subtree reports no errors.
+    pub fn collapsed(mut self, collapsed: bool) -> Self {
+        self.collapsed = collapsed;
+        self
+    }
+
+    /// Keep the solved layout of this node and its children after the
+    /// first solve. Later solves skip the subtree and only move it to its
+    /// new position, and resetting the tree doesn't clear it.
+    pub fn frozen(mut self, frozen: bool) -> Self {
+        self.frozen = frozen;
+        self.solved = false;
+        self
+    }
+
+    /// Align this node on its parent's cross axis, overriding the parent's
+    /// cross axis alignment.
+    pub fn cross_self(mut self, alignment: AxisAlignment) -> Self {
+        self.cross_self = Some(alignment);
+        self
+    }
+
+    /// Set the smallest size that the layout shrinks to, this only applies
+    /// to the axes that [shrink](BoxSizing::Shrink).
+    pub fn min_size(mut self, min_size: Size) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Sets the minimum touch target of this node, for interactive elements
+    /// such as buttons.
+    ///
+    /// After the layout has been positioned, the node will grow around its
+    /// center until it is at least this size, without moving its siblings.
+    /// If this pushes the node outside of its parent an
+    /// [`OutOfBounds`](LayoutError::OutOfBounds) error is reported.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, EmptyLayout, IntrinsicSize, Layout, Size};
+    ///
+    /// let mut button = EmptyLayout::new()
+    ///     .intrinsic_size(IntrinsicSize::fixed(20.0,20.0))
+    ///     .min_touch_target(Size::unit(44.0));
+    ///
+    /// solve_layout(&mut button, Size::unit(500.0));
+    /// assert_eq!(button.size(), Size::unit(44.0));
+    /// ```
+    pub fn min_touch_target(mut self, size: Size) -> Self {
+        self.min_touch_target = Some(size);
+        self
+    }
+
     impl_constraints!();
 }
 
@@ -38,27 +167,73 @@ impl Layout for EmptyLayout {
         self.label.clone().unwrap_or("EmptyLayout".to_string())
     }
 
+    fn short_label(&self) -> String {
+        self.label.clone().unwrap_or("E".to_string())
+    }
+
     fn solve_min_constraints(&mut self) -> (f32, f32) {
-        if let BoxSizing::Fixed(width) = self.intrinsic_size.width {
+        if self.frozen && self.solved {
+            return (self.constraints.min_width, self.constraints.min_height);
+        }
+
+        #[cfg(feature = "profiling")]
+        let _timer = crate::profiling::timer("EmptyLayout", crate::profiling::Pass::MinConstraints);
+
+        if let BoxSizing::Fixed(width) = self.get_intrinsic_size().width {
             self.constraints.min_width = width;
         }
 
-        if let BoxSizing::Fixed(height) = self.intrinsic_size.height {
+        if let BoxSizing::Fixed(height) = self.get_intrinsic_size().height {
             self.constraints.min_height = height;
         }
 
+        let intrinsic_size = self.get_intrinsic_size();
+        apply_min_size(&mut self.constraints, intrinsic_size, self.min_size);
         (self.constraints.min_width, self.constraints.min_height)
     }
 
     // No children to solve for
-    fn solve_max_constraints(&mut self, _: Size) {}
+    fn solve_max_constraints(&mut self, _: Size) {
+        #[cfg(feature = "profiling")]
+        let _timer = crate::profiling::timer("EmptyLayout", crate::profiling::Pass::MaxConstraints);
+    }
 
-    fn position_children(&mut self) {}
+    fn position_children(&mut self) {
+        #[cfg(feature = "profiling")]
+        let _timer = crate::profiling::timer("EmptyLayout", crate::profiling::Pass::Position);
+    }
+
+    fn expand_touch_target(&mut self, expand: bool) {
+        if let Some((position, size)) = self.unexpanded.take() {
+            self.position = position;
+            self.size = size;
+        }
+
+        let Some(target) = self.min_touch_target.filter(|_| expand && !self.collapsed) else {
+            return;
+        };
+        self.unexpanded = Some((self.position, self.size));
+        let width = self.size.width.max(target.width);
+        let height = self.size.height.max(target.height);
+        self.position.x -= (width - self.size.width) / 2.0;
+        self.position.y -= (height - self.size.height) / 2.0;
+        self.size = Size::new(width, height);
+    }
 
     fn update_size(&mut self) {
-        match self.intrinsic_size.width {
-            BoxSizing::Flex(_) => {
-                self.size.width = self.constraints.max_width.unwrap_or_default();
+        if self.frozen && self.solved {
+            return;
+        }
+
+        self.solved = self.frozen;
+        self.unexpanded = None;
+
+        match self.get_intrinsic_size().width {
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_) => {
+                self.size.width = flex_size(
+                    self.constraints.min_width,
+                    self.constraints.max_width.unwrap_or_default(),
+                );
             }
             BoxSizing::Shrink => {
                 self.size.width = self.constraints.min_width;
@@ -68,9 +243,10 @@ impl Layout for EmptyLayout {
             }
         }
 
-        match self.intrinsic_size.height {
-            BoxSizing::Flex(_) => {
-                self.size.height = self.constraints.max_height;
+        match self.get_intrinsic_size().height {
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_) => {
+                self.size.height =
+                    flex_size(self.constraints.min_height, self.constraints.max_height);
             }
             BoxSizing::Shrink => {
                 self.size.height = self.constraints.min_height;
@@ -85,6 +261,10 @@ impl Layout for EmptyLayout {
         self.errors.drain(..).collect::<Vec<_>>()
     }
 
+    fn collect_errors_into(&mut self, errors: &mut Vec<crate::LayoutError>) {
+        errors.append(&mut self.errors);
+    }
+
     fn id(&self) -> GlobalId {
         self.id
     }
@@ -94,7 +274,17 @@ impl Layout for EmptyLayout {
     }
 
     fn get_intrinsic_size(&self) -> IntrinsicSize {
-        self.intrinsic_size
+        if self.collapsed {
+            return IntrinsicSize::fixed(0.0, 0.0);
+        }
+        let mut intrinsic_size = match self.default_sizing {
+            Some(sizing) if self.auto => self.intrinsic_size.or_default(sizing),
+            _ => self.intrinsic_size,
+        };
+        if let Some(width) = self.pinned_width {
+            intrinsic_size.width = BoxSizing::Fixed(width);
+        }
+        intrinsic_size
     }
 
     fn size(&self) -> Size {
@@ -109,6 +299,10 @@ impl Layout for EmptyLayout {
         &[]
     }
 
+    fn children_mut(&mut self) -> &mut [Box<dyn Layout>] {
+        &mut []
+    }
+
     fn set_max_width(&mut self, width: f32) {
         self.constraints.max_width = Some(width);
     }
@@ -121,6 +315,29 @@ impl Layout for EmptyLayout {
         self.constraints.min_width = width;
     }
 
+    fn pin_width(&mut self, width: Option<f32>) {
+        self.pinned_width = width;
+    }
+
+    fn set_default_sizing(&mut self, sizing: BoxSizing) {
+        self.default_sizing = Some(sizing);
+    }
+
+    fn reset(&mut self) {
+        if self.frozen && self.solved {
+            return;
+        }
+
+        self.size = Size::default();
+        self.position = Position::default();
+        self.unexpanded = None;
+        self.constraints = BoxConstraints {
+            max_width: self.configured_max_width,
+            ..Default::default()
+        };
+        self.errors.clear();
+    }
+
     fn set_min_height(&mut self, height: f32) {
         self.constraints.min_height = height;
     }
@@ -129,6 +346,10 @@ impl Layout for EmptyLayout {
         self.position = position;
     }
 
+    fn set_size(&mut self, size: Size) {
+        self.size = size;
+    }
+
     fn set_x(&mut self, x: f32) {
         self.position.x = x;
     }
@@ -137,15 +358,64 @@ impl Layout for EmptyLayout {
         self.position.y = y;
     }
 
+    fn get_z_index(&self) -> i32 {
+        self.z_index
+    }
+
+    fn get_opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    fn is_collapsed(&self) -> bool {
+        self.collapsed
+    }
+
+    fn get_cross_self(&self) -> Option<AxisAlignment> {
+        self.cross_self
+    }
+
+    // No children to simplify
+    fn simplify(&mut self) {}
+
+    fn collapse(self: Box<Self>, removable: bool) -> Option<Box<dyn Layout>> {
+        let zero_sized = !self.auto
+            && !self.frozen
+            && self.min_touch_target.is_none()
+            && is_zero_sized(self.intrinsic_size);
+        if removable && zero_sized {
+            return None;
+        }
+        Some(self)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn Layout> {
+        Box::new(Self {
+            id: GlobalId::new(),
+            ..self.clone()
+        })
+    }
+
+    fn clone_with_ids(&self) -> Box<dyn Layout> {
+        Box::new(self.clone())
+    }
+
     fn iter(&self) -> LayoutIter<'_> {
         LayoutIter { stack: vec![self] }
     }
+
+    fn iter_lazy(&self) -> LazyLayoutIter<'_> {
+        LazyLayoutIter::new(self)
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::solve_layout;
+    use crate::{BlockLayout, HorizontalLayout, LayoutError, Padding, solve_layout};
 
     #[test]
     fn test_flex_sizing() {
@@ -182,4 +452,76 @@ mod test {
 
         assert_eq!(root.size(), Size::default());
     }
+
+    #[test]
+    fn grow_to_touch_target() {
+        let child = EmptyLayout::new()
+            .intrinsic_size(IntrinsicSize::fixed(20.0, 20.0))
+            .min_touch_target(Size::unit(44.0));
+        let mut root = BlockLayout::new(child)
+            .padding(Padding::all(50.0))
+            .intrinsic_size(IntrinsicSize::fill());
+
+        let errors = solve_layout(&mut root, Size::unit(500.0));
+
+        let child = root.child();
+        assert!(errors.is_empty());
+        assert_eq!(child.size(), Size::unit(44.0));
+        assert_eq!(child.position(), Position::unit(50.0 - 12.0));
+    }
+
+    #[test]
+    fn touch_target_out_of_bounds() {
+        let child = EmptyLayout::new()
+            .intrinsic_size(IntrinsicSize::fixed(20.0, 20.0))
+            .min_touch_target(Size::unit(44.0));
+        let mut root = HorizontalLayout::new().add_child(child);
+
+        let errors = solve_layout(&mut root, Size::unit(500.0));
+
+        assert!(matches!(errors[0], LayoutError::OutOfBounds { .. }));
+    }
+
+    #[test]
+    fn reposition_touch_target() {
+        let child = EmptyLayout::new()
+            .intrinsic_size(IntrinsicSize::fixed(20.0, 20.0))
+            .min_touch_target(Size::unit(44.0));
+        let mut root = HorizontalLayout::new()
+            .padding(Padding::all(50.0))
+            .add_children([child.clone(), child]);
+
+        solve_layout(&mut root, Size::unit(500.0));
+        let errors = root.reposition();
+        let errors_again = root.reposition();
+
+        assert!(errors.is_empty());
+        assert!(errors_again.is_empty());
+        assert_eq!(root.children()[0].position(), Position::unit(50.0 - 12.0));
+        assert_eq!(
+            root.children()[1].position(),
+            Position::new(70.0 - 12.0, 38.0)
+        );
+        assert_eq!(root.children()[1].size(), Size::unit(44.0));
+    }
+
+    #[test]
+    fn adjacent_touch_targets() {
+        let child = EmptyLayout::new()
+            .intrinsic_size(IntrinsicSize::fixed(20.0, 20.0))
+            .min_touch_target(Size::unit(44.0));
+        let mut root = HorizontalLayout::new()
+            .padding(Padding::all(12.0))
+            .spacing(24)
+            .add_children([child.clone(), child]);
+
+        let errors = solve_layout(&mut root, Size::unit(500.0));
+
+        let first = root.children()[0].bounds();
+        let second = root.children()[1].bounds();
+        assert!(errors.is_empty());
+        assert_eq!(first.x, [0.0, 44.0]);
+        assert_eq!(second.x, [44.0, 88.0]);
+        assert_eq!(root.size(), Size::new(88.0, 44.0));
+    }
 }
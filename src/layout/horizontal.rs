@@ -1,7 +1,12 @@
+use super::{
+    apply_min_size, flex_shares, flex_size, gap_before, gap_count, is_zero_sized, overflows,
+    position_child, remap_links,
+};
 use crate::constraints::impl_constraints;
 use crate::{
-    AxisAlignment, BoxConstraints, BoxSizing, GlobalId, IntrinsicSize, Layout, LayoutError,
-    LayoutIter, Padding, Position, Size,
+    AxisAlignment, Border, Bounds, BoxConstraints, BoxSizing, CrossSizing, GlobalId, IntrinsicSize,
+    Layout, LayoutError, LayoutIter, LazyLayoutIter, MainAxisSize, Padding, Position,
+    RelativePadding, ScrollState, Size, error::OverflowAxis,
 };
 
 /// A [`Layout`] that arranges it's child nodes horizontally.
@@ -16,15 +21,32 @@ use crate::{
 ///     .padding(Padding::symmetric(10.0,20.0))
 ///     .spacing(12);
 /// ```
-#[derive(Default, Debug)]
+#[derive(Debug)]
 pub struct HorizontalLayout {
     id: GlobalId,
     size: Size,
     position: Position,
     spacing: u32,
+    collapsible_spacing: bool,
+    justify_fill: bool,
+    report_overflow: bool,
+    round_flex: bool,
+    cross_sizing: CrossSizing,
+    stretch_cross_axis: bool,
+    main_axis_size: MainAxisSize,
+    /// Pairs of nodes in the subtree, the second copies the width of the first.
+    width_links: Vec<(GlobalId, GlobalId)>,
+    scroll_offset: f32,
     padding: Padding,
+    /// The padding with percentages, which is resolved into `padding`.
+    relative_padding: Option<RelativePadding>,
+    border: Border,
     constraints: BoxConstraints,
+    /// The max width set by the user, which is kept when the layout is reset.
+    configured_max_width: Option<f32>,
     intrinsic_size: IntrinsicSize,
+    auto: bool,
+    default_sizing: Option<BoxSizing>,
     /// The main axis is the axis which the content flows in, for the [`HorizontalLayout`]
     /// main axis is the `x-axis`
     main_axis_alignment: AxisAlignment,
@@ -33,6 +55,61 @@ pub struct HorizontalLayout {
     children: Vec<Box<dyn Layout>>,
     errors: Vec<LayoutError>,
     label: Option<String>,
+    z_index: i32,
+    opacity: f32,
+    hidden: bool,
+    /// The width given by a width link, which replaces the intrinsic width.
+    pinned_width: Option<f32>,
+    collapsed: bool,
+    /// Whether the solved layout is kept after the first solve.
+    frozen: bool,
+    /// Whether a frozen layout has been solved.
+    solved: bool,
+    cross_self: Option<AxisAlignment>,
+    /// The smallest size of the axes that shrink.
+    min_size: Size,
+}
+
+impl Default for HorizontalLayout {
+    fn default() -> Self {
+        Self {
+            id: GlobalId::new(),
+            size: Size::default(),
+            position: Position::default(),
+            spacing: 0,
+            collapsible_spacing: false,
+            justify_fill: false,
+            report_overflow: true,
+            round_flex: false,
+            cross_sizing: CrossSizing::default(),
+            stretch_cross_axis: false,
+            main_axis_size: MainAxisSize::default(),
+            width_links: vec![],
+            scroll_offset: 0.0,
+            padding: Padding::default(),
+            relative_padding: None,
+            border: Border::default(),
+            constraints: BoxConstraints::default(),
+            configured_max_width: None,
+            intrinsic_size: IntrinsicSize::default(),
+            auto: false,
+            default_sizing: None,
+            main_axis_alignment: AxisAlignment::default(),
+            cross_axis_alignment: AxisAlignment::default(),
+            children: vec![],
+            errors: vec![],
+            label: None,
+            z_index: 0,
+            opacity: 1.0,
+            hidden: false,
+            pinned_width: None,
+            collapsed: false,
+            frozen: false,
+            solved: false,
+            cross_self: None,
+            min_size: Size::default(),
+        }
+    }
 }
 
 impl HorizontalLayout {
@@ -40,6 +117,49 @@ impl HorizontalLayout {
         Self::default()
     }
 
+    /// Creates a new [`HorizontalLayout`] from a list of boxed child nodes, which
+    /// allows for children of different types.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{HorizontalLayout,BlockLayout,EmptyLayout,Layout};
+    ///
+    /// let layout = HorizontalLayout::with_children(vec![
+    ///     Box::new(EmptyLayout::new()),
+    ///     Box::new(BlockLayout::new(EmptyLayout::new())),
+    /// ]);
+    ///
+    /// assert_eq!(layout.children().len(),2);
+    /// ```
+    pub fn with_children(children: Vec<Box<dyn Layout>>) -> Self {
+        Self {
+            children,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new [`HorizontalLayout`] with `n` equal columns. Each column
+    /// is an [`EmptyLayout`](crate::EmptyLayout) that fills its share of the
+    /// width and the height of the row, which can be replaced using
+    /// [`children_mut`](Layout::children_mut).
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, HorizontalLayout, IntrinsicSize, Layout, Size};
+    ///
+    /// let mut layout = HorizontalLayout::columns(2).intrinsic_size(IntrinsicSize::fill());
+    /// solve_layout(&mut layout, Size::new(500.0, 100.0));
+    ///
+    /// assert_eq!(layout.children()[1].size(), Size::new(250.0, 100.0));
+    /// ```
+    pub fn columns(n: usize) -> Self {
+        let children = (0..n)
+            .map(|_| crate::EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()))
+            .map(Layout::boxed)
+            .collect();
+        Self::with_children(children)
+    }
+
     pub fn set_id(mut self, id: GlobalId) -> Self {
         self.id = id;
         self
@@ -65,6 +185,82 @@ impl HorizontalLayout {
         self
     }
 
+    /// Set the paint order of the node relative to its siblings in the
+    /// [flattened](Layout::flatten) tree, nodes with a higher `z_index`
+    /// are drawn later. This does not affect the layout.
+    pub fn z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+
+    /// Set the opacity of the node, which is clamped between `0.0` and `1.0`.
+    /// This is passed on to the [flattened](Layout::flatten) tree and does
+    /// not affect the layout.
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Hide the node and its children from the [flattened](Layout::flatten)
+    /// tree. Hidden nodes still take up space in the layout.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Collapse the node, so that it takes up no space in the layout and
+    /// the parent leaves out the spacing around it. Unlike
+    /// [`hidden`](Self::hidden), which only skips drawing the node, this
+    /// removes it from the layout. A collapsed subtree reports no errors.
+    pub fn collapsed(mut self, collapsed: bool) -> Self {
+        self.collapsed = collapsed;
+        self
+    }
+
+    /// Keep the solved layout of this node and its children after the
+    /// first solve. Later solves skip the subtree and only move it to its
+    /// new position, and resetting the tree doesn't clear it.
+    pub fn frozen(mut self, frozen: bool) -> Self {
+        self.frozen = frozen;
+        self.solved = false;
+        self
+    }
+
+    /// Align this node on its parent's cross axis, overriding the parent's
+    /// cross axis alignment.
+    pub fn cross_self(mut self, alignment: AxisAlignment) -> Self {
+        self.cross_self = Some(alignment);
+        self
+    }
+
+    /// Set the smallest size that the layout shrinks to, this only applies
+    /// to the axes that [shrink](BoxSizing::Shrink).
+    pub fn min_size(mut self, min_size: Size) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Appends a boxed [`Layout`] node to the list of children.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{HorizontalLayout,EmptyLayout,Layout};
+    ///
+    /// let child: Box<dyn Layout> = Box::new(EmptyLayout::new());
+    /// HorizontalLayout::new()
+    ///     .add_boxed_child(child);
+    /// ```
+    pub fn add_boxed_child(mut self, child: Box<dyn Layout>) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Appends multiple boxed [`Layout`] nodes to the list of children.
+    pub fn add_boxed_children(mut self, children: Vec<Box<dyn Layout>>) -> Self {
+        self.children.extend(children);
+        self
+    }
+
     /// Add multiple child nodes to the list of children.
     ///
     /// # Example
@@ -89,17 +285,197 @@ impl HorizontalLayout {
     }
 
     /// Sets this layout's [`Padding`].
-    pub fn padding(mut self, padding: Padding) -> Self {
-        self.padding = padding;
+    ///
+    /// The padding can also be a [`RelativePadding`], with sides that are
+    /// a percentage of the space the layout is given by its parent.
+    pub fn padding(mut self, padding: impl Into<RelativePadding>) -> Self {
+        let padding = padding.into();
+        self.padding = padding.resolve(Size::default());
+        self.relative_padding = padding.is_relative().then_some(padding);
+        self
+    }
+
+    /// Sets this layout's [`Border`], the content is inset by the border
+    /// in addition to the padding.
+    pub fn border(mut self, border: Border) -> Self {
+        self.border = border;
         self
     }
 
     /// Sets this layout's spacing.
+    ///
+    /// The spacing is only added between the children, not before the first
+    /// child or after the last, the padding is the only space between the
+    /// edges of the layout and its children. Hidden children keep their
+    /// size but have no spacing around them, so the spacing is only added
+    /// between consecutive visible children. The minimum width of the layout
+    /// is the padding, plus the width of the children, plus the spacing
+    /// between them.
     pub fn spacing(mut self, spacing: u32) -> Self {
         self.spacing = spacing;
         self
     }
 
+    /// Allow the spacing to shrink, down to zero, when the children would
+    /// otherwise overflow the main axis.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, Size};
+    ///
+    /// let child = EmptyLayout::new()
+    ///     .intrinsic_size(IntrinsicSize::fixed(150.0,150.0));
+    ///
+    /// let mut layout = HorizontalLayout::new()
+    ///     .intrinsic_size(IntrinsicSize::fixed(460.0,150.0))
+    ///     .spacing(40)
+    ///     .collapsible_spacing(true)
+    ///     .add_children([child.clone(),child.clone(),child]);
+    ///
+    /// solve_layout(&mut layout,Size::unit(1000.0));
+    ///
+    /// assert_eq!(layout.children()[2].position().x,310.0);
+    /// ```
+    pub fn collapsible_spacing(mut self, collapsible: bool) -> Self {
+        self.collapsible_spacing = collapsible;
+        self
+    }
+
+    /// Place the children at the start of the main axis and leave all the
+    /// remaining space after the last child, the main axis alignment is
+    /// ignored. A layout that shrinks on the main axis grows to its max
+    /// width so that the remaining space is part of the layout.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, AxisAlignment, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, Size};
+    ///
+    /// let mut layout = HorizontalLayout::new()
+    ///     .main_axis_alignment(AxisAlignment::Center)
+    ///     .justify_fill(true)
+    ///     .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0,50.0)));
+    ///
+    /// solve_layout(&mut layout, Size::unit(500.0));
+    ///
+    /// assert_eq!(layout.size().width, 500.0);
+    /// assert_eq!(layout.children()[0].position().x, 0.0);
+    /// ```
+    pub fn justify_fill(mut self, justify_fill: bool) -> Self {
+        self.justify_fill = justify_fill;
+        self
+    }
+
+    /// Set whether [`Overflow`](LayoutError::Overflow) errors are reported
+    /// for this layout, which is `true` by default. The children are still
+    /// sized and positioned in the same way, this is useful for nodes that
+    /// are meant to overflow.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, EmptyLayout, HorizontalLayout, IntrinsicSize, Size};
+    ///
+    /// let mut layout = HorizontalLayout::new()
+    ///     .intrinsic_size(IntrinsicSize::fixed(50.0, 50.0))
+    ///     .report_overflow(false)
+    ///     .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 100.0)));
+    ///
+    /// let errors = solve_layout(&mut layout, Size::unit(500.0));
+    /// assert!(errors.is_empty());
+    /// ```
+    pub fn report_overflow(mut self, report_overflow: bool) -> Self {
+        self.report_overflow = report_overflow;
+        self
+    }
+
+    /// Round the space given to flex children to whole pixels. The
+    /// leftover pixels are given to the first flex children, one each, so
+    /// the children fill the available space exactly.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, Size};
+    ///
+    /// let mut layout = HorizontalLayout::new()
+    ///     .intrinsic_size(IntrinsicSize::fill())
+    ///     .round_flex(true)
+    ///     .add_children([
+    ///         EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()),
+    ///         EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()),
+    ///     ]);
+    ///
+    /// solve_layout(&mut layout, Size::unit(101.0));
+    /// assert_eq!(layout.children()[0].size().width, 51.0);
+    /// assert_eq!(layout.children()[1].size().width, 50.0);
+    /// ```
+    pub fn round_flex(mut self, round_flex: bool) -> Self {
+        self.round_flex = round_flex;
+        self
+    }
+
+    /// Sets how the height is taken from the children, when the layout
+    /// shrinks on the cross axis.
+    pub fn cross_sizing(mut self, cross_sizing: CrossSizing) -> Self {
+        self.cross_sizing = cross_sizing;
+        self
+    }
+
+    /// Stretch the children that don't have a fixed height to the height of
+    /// the layout, inside the padding, without changing the cross axis
+    /// alignment. Children with their own [`cross_self`](Layout::get_cross_self)
+    /// alignment are left as they are.
+    pub fn stretch_cross_axis(mut self, stretch: bool) -> Self {
+        self.stretch_cross_axis = stretch;
+        self
+    }
+
+    /// Sets how big the layout is on the main axis when it flexes. With
+    /// [`MainAxisSize::Min`] the layout is still given space by its parent
+    /// as a flex node, but its width is the width of its children.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, MainAxisSize, Size};
+    ///
+    /// let mut layout = HorizontalLayout::new()
+    ///     .intrinsic_size(IntrinsicSize::fill())
+    ///     .main_axis_size(MainAxisSize::Min)
+    ///     .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0)));
+    ///
+    /// solve_layout(&mut layout, Size::unit(500.0));
+    /// assert_eq!(layout.size().width, 50.0);
+    /// ```
+    pub fn main_axis_size(mut self, main_axis_size: MainAxisSize) -> Self {
+        self.main_axis_size = main_axis_size;
+        self
+    }
+
+    /// Link the width of two nodes in the subtree, once the tree has been
+    /// sized the node with the id `b` takes the width of the node with the
+    /// id `a`. The tree is then solved again with both nodes fixed to that
+    /// width, so the subtree of `b` is sized to fit it and its siblings are
+    /// moved to make room.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, Size};
+    ///
+    /// let a = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(200.0, 50.0));
+    /// let b = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+    /// let (a_id, b_id) = (a.id(), b.id());
+    ///
+    /// let mut layout = HorizontalLayout::new()
+    ///     .add_child(a)
+    ///     .add_child(b)
+    ///     .link_width(a_id, b_id);
+    ///
+    /// solve_layout(&mut layout, Size::unit(500.0));
+    /// assert_eq!(layout.children()[1].size().width, 200.0);
+    /// ```
+    pub fn link_width(mut self, a: GlobalId, b: GlobalId) -> Self {
+        self.width_links.push((a, b));
+        self
+    }
+
     /// Sets the main axis alignment
     pub fn main_axis_alignment(mut self, main_axis_alignment: AxisAlignment) -> Self {
         self.main_axis_alignment = main_axis_alignment;
@@ -112,35 +488,73 @@ impl HorizontalLayout {
         self
     }
 
+    /// Center the children on both the main and cross axis.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, Position, Size};
+    ///
+    /// let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+    /// let mut layout = HorizontalLayout::new()
+    ///     .intrinsic_size(IntrinsicSize::fixed(200.0, 200.0))
+    ///     .center()
+    ///     .add_child(child);
+    ///
+    /// solve_layout(&mut layout, Size::unit(500.0));
+    /// assert_eq!(layout.children()[0].position(), Position::unit(75.0));
+    /// ```
+    pub fn center(mut self) -> Self {
+        self.main_axis_alignment = AxisAlignment::Center;
+        self.cross_axis_alignment = AxisAlignment::Center;
+        self
+    }
+
     /// Calculate the total minimum constraints of all
     /// the child nodes. The width is the sum of all
     /// the children's minimum width plus the space in
-    /// between. The height is gotten from the largest
-    /// of the minimum height.
+    /// between. The height is resolved from the children's
+    /// minimum heights by the [`CrossSizing`], which is also
+    /// how `content_extent` resolves the solved heights when
+    /// checking for overflow.
     fn compute_children_min_size(&mut self) -> Size {
         let mut sum = Size::default();
-        if self.children.is_empty() {
-            return sum;
-        }
+        sum.width += self.insets().horizontal_sum();
+        sum.height += self.insets().vertical_sum();
 
-        let space_between = (self.children.len() - 1) as f32 * self.spacing as f32;
+        let space_between = gap_count(&self.children) as f32 * self.spacing as f32;
         sum.width += space_between;
+        let mut heights = Vec::with_capacity(self.children.len());
         for child in self.children.iter_mut() {
             let (min_width, min_height) = child.solve_min_constraints();
             sum.width += min_width;
-            sum.height = sum.height.max(min_height);
+            heights.push(min_height);
         }
-        sum.width += self.padding.horizontal_sum();
-        sum.height += self.padding.vertical_sum();
+        sum.height += self.cross_sizing.resolve(&heights);
         sum
     }
 
+    /// The size of the solved children including the padding and the
+    /// spacing between them, with the height resolved by the
+    /// [`CrossSizing`]. The layout overflows if it is smaller than this.
+    fn content_extent(&self) -> Size {
+        let mut extent = Size::new(self.insets().horizontal_sum(), self.insets().vertical_sum());
+        let gaps = gap_count(&self.children) as f32;
+        extent.width += gaps * self.resolved_spacing();
+        let mut heights = Vec::with_capacity(self.children.len());
+        for child in &self.children {
+            extent.width += child.size().width;
+            heights.push(child.size().height);
+        }
+        extent.height += self.cross_sizing.resolve(&heights);
+        extent
+    }
+
     /// Calculate the sum of the width's of all nodes with fixed sizes and the max height
     fn fixed_size_sum(&self) -> Size {
         let mut sum = Size::default();
 
-        for (i, child) in self.children.iter().enumerate() {
-            match child.get_intrinsic_size().width {
+        for child in &self.children {
+            match sharing_width(child.as_ref()) {
                 BoxSizing::Fixed(width) => {
                     sum.width += width;
                 }
@@ -153,23 +567,25 @@ impl HorizontalLayout {
             if let BoxSizing::Fixed(height) = child.get_intrinsic_size().height {
                 sum.height = sum.height.max(height);
             }
-
-            // Add the spacing between layouts
-            if i != self.children.len() - 1 {
-                sum.width += self.spacing as f32;
-            }
         }
 
+        // Add the spacing between layouts
+        sum.width += gap_count(&self.children) as f32 * self.spacing as f32;
         sum
     }
 
     fn align_main_axis_start(&mut self) {
+        let spacing = self.resolved_spacing();
         let mut x_pos = self.position.x;
-        x_pos += self.padding.left;
+        x_pos += self.insets().left;
 
+        let mut after_visible = false;
         for child in &mut self.children {
+            if gap_before(child.as_ref(), &mut after_visible) {
+                x_pos += spacing;
+            }
             child.set_x(x_pos);
-            x_pos += child.size().width + self.spacing as f32;
+            x_pos += child.size().width;
         }
     }
 
@@ -186,63 +602,256 @@ impl HorizontalLayout {
             .map(|child| child.size().width)
             .sum::<f32>();
         // Add the spacing in between each child
-        let space_between = self.spacing * (self.children.len() - 1) as u32;
-        width_sum += space_between as f32;
+        let spacing = self.resolved_spacing();
+        width_sum += spacing * gap_count(&self.children) as f32;
         let mut center_start = self.position.x + (self.size.width - width_sum) / 2.0;
 
+        let mut after_visible = false;
         for child in &mut self.children {
+            if gap_before(child.as_ref(), &mut after_visible) {
+                center_start += spacing;
+            }
             child.set_x(center_start);
-            center_start += child.size().width + self.spacing as f32;
+            center_start += child.size().width;
         }
     }
 
     fn align_main_axis_end(&mut self) {
+        let spacing = self.resolved_spacing();
         let mut x_pos = self.position.x + self.size.width;
-        x_pos -= self.padding.right;
+        x_pos -= self.insets().right;
 
+        let mut after_visible = false;
         for child in self.children.iter_mut().rev() {
+            if gap_before(child.as_ref(), &mut after_visible) {
+                x_pos -= spacing;
+            }
             // Set the right edge
             x_pos -= child.size().width;
             child.set_x(x_pos);
-            x_pos -= self.spacing as f32;
         }
     }
 
-    fn align_cross_axis_start(&mut self) {
-        let y = self.position.y + self.padding.top;
+    /// Align the children on the cross axis, children with their own
+    /// [`cross_self`](Layout::get_cross_self) alignment override the
+    /// layout's alignment.
+    fn align_cross_axis(&mut self) {
+        let insets = self.insets();
         for child in &mut self.children {
+            let alignment = child.get_cross_self().unwrap_or(self.cross_axis_alignment);
+            let y = match alignment {
+                AxisAlignment::Start | AxisAlignment::Stretch => self.position.y + insets.top,
+                AxisAlignment::Center => {
+                    (self.size.height - child.size().height) / 2.0 + self.position.y
+                }
+                AxisAlignment::End => {
+                    self.position.y + self.size.height - insets.bottom - child.size().height
+                }
+            };
             child.set_y(y);
         }
     }
 
-    fn align_cross_axis_center(&mut self) {
-        for child in &mut self.children {
-            let y_pos = (self.size.height - child.size().height) / 2.0 + self.position.y;
-            child.set_y(y_pos);
-        }
+    /// Scrolls the children along the main axis by `delta`. The offset is
+    /// applied the next time the children are positioned.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, Size};
+    ///
+    /// let mut layout = HorizontalLayout::new()
+    ///     .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0,50.0)));
+    ///
+    /// layout.scroll(-20.0);
+    /// solve_layout(&mut layout, Size::unit(500.0));
+    ///
+    /// assert_eq!(layout.scroll_offset(), -20.0);
+    /// assert_eq!(layout.children()[0].position().x, -20.0);
+    /// ```
+    pub fn scroll(&mut self, delta: f32) {
+        self.scroll_offset += delta;
     }
 
-    fn align_cross_axis_end(&mut self) {
-        for child in &mut self.children {
-            child.set_y(self.position.y + self.size.height - self.padding.bottom);
+    /// Returns the current scroll offset.
+    pub fn scroll_offset(&self) -> f32 {
+        self.scroll_offset
+    }
+
+    /// Take a snapshot of the scroll offset, to carry it across solves with
+    /// [`restore_scroll`](HorizontalLayout::restore_scroll).
+    pub fn scroll_state(&self) -> ScrollState {
+        ScrollState {
+            offset: self.scroll_offset,
         }
     }
 
-    /// Sum up all the flex factors
-    fn flex_total(&self) -> u8 {
-        // TODO: if max width is set should we exclude
-        // from flex factor?
+    /// Restore a scroll offset from a [`ScrollState`], clamped so that the
+    /// content can't be scrolled past its ends. The clamp uses the solved
+    /// sizes, so this should be called after solving, followed by
+    /// [`reposition`](Layout::reposition) to move the children.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, Size};
+    ///
+    /// let item = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 100.0));
+    /// let mut layout = HorizontalLayout::new()
+    ///     .intrinsic_size(IntrinsicSize::fixed(200.0, 200.0))
+    ///     .report_overflow(false)
+    ///     .add_children([item.clone(), item.clone(), item]);
+    ///
+    /// layout.scroll(-500.0);
+    /// let state = layout.scroll_state();
+    /// solve_layout(&mut layout, Size::unit(500.0));
+    ///
+    /// layout.restore_scroll(state);
+    /// layout.reposition();
+    /// assert_eq!(layout.scroll_offset(), -100.0);
+    /// ```
+    pub fn restore_scroll(&mut self, state: ScrollState) {
+        let max_scroll = self.overflow_amount().width;
+        self.scroll_offset = state.offset.clamp(-max_scroll, 0.0);
+    }
 
+    /// Returns the id of the visible child whose span on the main axis
+    /// contains `offset`. The offset is measured from the start of the
+    /// layout, before scrolling, so the padding and the spacing between
+    /// children are part of it. Returns `None` if the offset falls in
+    /// the padding or in a gap. Uses the solved positions, so it is only
+    /// meaningful after the layout is solved.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, Size};
+    ///
+    /// let item = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 100.0));
+    /// let mut layout = HorizontalLayout::new()
+    ///     .add_children([item.clone(), item.clone(), item]);
+    ///
+    /// solve_layout(&mut layout, Size::unit(500.0));
+    ///
+    /// let id = layout.children()[1].id();
+    /// assert_eq!(layout.child_at_main_offset(150.0), Some(id));
+    /// ```
+    pub fn child_at_main_offset(&self, offset: f32) -> Option<GlobalId> {
+        let offset = self.position.x + self.scroll_offset + offset;
         self.children
             .iter()
-            .filter_map(|child| {
-                if let BoxSizing::Flex(factor) = child.get_intrinsic_size().width {
-                    Some(factor)
-                } else {
-                    None
-                }
+            .filter(|child| !child.is_hidden())
+            .find(|child| {
+                let start = child.position().x;
+                offset >= start && offset < start + child.size().width
             })
-            .sum()
+            .map(|child| child.id())
+    }
+
+    /// Returns how far the children extend past the edges of the layout,
+    /// on each axis, including the padding and spacing. This is zero on
+    /// an axis that isn't overflowing. The amount is computed from the
+    /// solved sizes, so it is only meaningful after the layout is solved.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, EmptyLayout, HorizontalLayout, IntrinsicSize, Size};
+    ///
+    /// let mut layout = HorizontalLayout::new()
+    ///     .intrinsic_size(IntrinsicSize::fixed(100.0, 100.0))
+    ///     .add_children([EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 120.0)), EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 120.0)), EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 120.0))]);
+    ///
+    /// solve_layout(&mut layout, Size::unit(500.0));
+    /// assert_eq!(layout.overflow_amount(), Size::new(50.0, 20.0));
+    /// ```
+    pub fn overflow_amount(&self) -> Size {
+        let extent = self.content_extent();
+        Size::new(
+            (extent.width - self.size.width).max(0.0),
+            (extent.height - self.size.height).max(0.0),
+        )
+    }
+
+    /// Returns the spacing that is actually applied between the children,
+    /// which is less than the set spacing if the spacing has collapsed.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, EmptyLayout, HorizontalLayout, IntrinsicSize, Size};
+    ///
+    /// let child = EmptyLayout::new()
+    ///     .intrinsic_size(IntrinsicSize::fixed(150.0,150.0));
+    ///
+    /// let mut layout = HorizontalLayout::new()
+    ///     .intrinsic_size(IntrinsicSize::fixed(460.0,150.0))
+    ///     .spacing(40)
+    ///     .collapsible_spacing(true)
+    ///     .add_children([child.clone(),child.clone(),child]);
+    ///
+    /// solve_layout(&mut layout,Size::unit(1000.0));
+    ///
+    /// assert_eq!(layout.resolved_spacing(),5.0);
+    /// ```
+    pub fn resolved_spacing(&self) -> f32 {
+        let spacing = self.spacing as f32;
+        let gaps = gap_count(&self.children) as f32;
+        if !self.collapsible_spacing || gaps == 0.0 {
+            return spacing;
+        }
+
+        let mut available_space = self.size.width - self.insets().horizontal_sum();
+        available_space -= self
+            .children
+            .iter()
+            .map(|child| child.size().width)
+            .sum::<f32>();
+
+        (available_space / gaps).clamp(0.0, spacing)
+    }
+
+    /// Copies the configuration and state of the layout, with
+    /// a different `id` and children.
+    fn clone_with(&self, id: GlobalId, children: Vec<Box<dyn Layout>>) -> Self {
+        Self {
+            id,
+            size: self.size,
+            position: self.position,
+            spacing: self.spacing,
+            collapsible_spacing: self.collapsible_spacing,
+            justify_fill: self.justify_fill,
+            report_overflow: self.report_overflow,
+            round_flex: self.round_flex,
+            cross_sizing: self.cross_sizing,
+            stretch_cross_axis: self.stretch_cross_axis,
+            main_axis_size: self.main_axis_size,
+            width_links: self.width_links.clone(),
+            scroll_offset: self.scroll_offset,
+            padding: self.padding,
+            relative_padding: self.relative_padding,
+            border: self.border,
+            constraints: self.constraints,
+            configured_max_width: self.configured_max_width,
+            intrinsic_size: self.intrinsic_size,
+            auto: self.auto,
+            default_sizing: self.default_sizing,
+            main_axis_alignment: self.main_axis_alignment,
+            cross_axis_alignment: self.cross_axis_alignment,
+            children,
+            errors: self.errors.clone(),
+            label: self.label.clone(),
+            z_index: self.z_index,
+            opacity: self.opacity,
+            hidden: self.hidden,
+            pinned_width: self.pinned_width,
+            collapsed: self.collapsed,
+            frozen: self.frozen,
+            solved: self.solved,
+            cross_self: self.cross_self,
+            min_size: self.min_size,
+        }
+    }
+
+    /// The total space between the edges of the layout and its
+    /// content, which is the padding plus the border.
+    fn insets(&self) -> Padding {
+        self.padding.with_border(self.border)
     }
 
     impl_constraints!();
@@ -253,10 +862,18 @@ impl Layout for HorizontalLayout {
         self.label.clone().unwrap_or("HorizontalLayout".to_string())
     }
 
+    fn short_label(&self) -> String {
+        self.label.clone().unwrap_or("H".to_string())
+    }
+
     fn id(&self) -> GlobalId {
         self.id
     }
 
+    fn set_size(&mut self, size: Size) {
+        self.size = size;
+    }
+
     fn set_x(&mut self, x: f32) {
         self.position.x = x;
     }
@@ -273,16 +890,59 @@ impl Layout for HorizontalLayout {
         self.position
     }
 
+    fn content_bounds(&self) -> Bounds {
+        self.bounds().inset_by(self.insets())
+    }
+
     fn children(&self) -> &[Box<dyn Layout>] {
         self.children.as_slice()
     }
 
+    fn children_mut(&mut self) -> &mut [Box<dyn Layout>] {
+        self.children.as_mut_slice()
+    }
+
     fn constraints(&self) -> BoxConstraints {
         self.constraints
     }
 
     fn get_intrinsic_size(&self) -> IntrinsicSize {
-        self.intrinsic_size
+        if self.collapsed {
+            return IntrinsicSize::fixed(0.0, 0.0);
+        }
+        let mut intrinsic_size = match self.default_sizing {
+            Some(sizing) if self.auto => self.intrinsic_size.or_default(sizing),
+            _ => self.intrinsic_size,
+        };
+        if let Some(width) = self.pinned_width {
+            intrinsic_size.width = BoxSizing::Fixed(width);
+        }
+        intrinsic_size
+    }
+
+    fn get_padding(&self) -> Padding {
+        self.padding
+    }
+
+    #[cfg(feature = "taffy-interop")]
+    fn to_taffy_style(&self) -> taffy::Style {
+        let base = crate::taffy_interop::base_style(
+            self.get_intrinsic_size(),
+            self.padding,
+            self.border,
+            self.cross_self,
+        );
+        crate::taffy_interop::flex_style(
+            base,
+            taffy::FlexDirection::Row,
+            self.spacing,
+            self.main_axis_alignment,
+            self.cross_axis_alignment,
+        )
+    }
+
+    fn get_border(&self) -> Border {
+        self.border
     }
 
     fn set_max_height(&mut self, height: f32) {
@@ -301,78 +961,238 @@ impl Layout for HorizontalLayout {
         self.constraints.min_width = width;
     }
 
+    fn pin_width(&mut self, width: Option<f32>) {
+        self.pinned_width = width;
+    }
+
+    fn set_default_sizing(&mut self, sizing: BoxSizing) {
+        self.default_sizing = Some(sizing);
+        for child in &mut self.children {
+            child.set_default_sizing(sizing);
+        }
+    }
+
+    fn reset(&mut self) {
+        if self.frozen && self.solved {
+            return;
+        }
+
+        self.size = Size::default();
+        self.position = Position::default();
+        if let Some(padding) = self.relative_padding {
+            self.padding = padding.resolve(Size::default());
+        }
+        self.constraints = BoxConstraints {
+            max_width: self.configured_max_width,
+            ..Default::default()
+        };
+        self.errors.clear();
+        for child in &mut self.children {
+            child.reset();
+        }
+    }
+
     fn collect_errors(&mut self) -> Vec<LayoutError> {
-        self.errors
-            .drain(..)
-            .chain(
-                self.children
-                    .iter_mut()
-                    .flat_map(|child| child.collect_errors()),
-            )
-            .collect::<Vec<_>>()
+        let mut errors = vec![];
+        self.collect_errors_into(&mut errors);
+        errors
+    }
+
+    fn collect_errors_into(&mut self, errors: &mut Vec<LayoutError>) {
+        let start = errors.len();
+        errors.append(&mut self.errors);
+        for child in &mut self.children {
+            child.collect_errors_into(errors);
+        }
+        // A collapsed subtree has no space to fit in
+        if self.collapsed {
+            errors.truncate(start);
+        }
+    }
+
+    fn get_z_index(&self) -> i32 {
+        self.z_index
+    }
+
+    fn get_opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    fn is_collapsed(&self) -> bool {
+        self.collapsed
+    }
+
+    fn width_links(&self) -> &[(GlobalId, GlobalId)] {
+        &self.width_links
+    }
+
+    fn get_cross_self(&self) -> Option<AxisAlignment> {
+        self.cross_self
+    }
+
+    fn clone_boxed(&self) -> Box<dyn Layout> {
+        let children = self
+            .children
+            .iter()
+            .map(|child| child.clone_boxed())
+            .collect();
+        let mut layout = self.clone_with(GlobalId::new(), children);
+        layout.width_links = remap_links(&self.width_links, self, &layout);
+        Box::new(layout)
+    }
+
+    fn simplify(&mut self) {
+        // Zero sized children still take up the spacing around them, and
+        // may be used to size the cross axis
+        let removable = self.spacing == 0
+            && matches!(self.cross_sizing, CrossSizing::Max | CrossSizing::Fixed(_));
+        let children = std::mem::take(&mut self.children);
+        self.children = children
+            .into_iter()
+            .filter_map(|mut child| {
+                child.simplify();
+                let id = child.id();
+                let linked = self.width_links.iter().any(|(a, b)| *a == id || *b == id);
+                child.collapse(removable && !linked)
+            })
+            .collect();
+    }
+
+    fn collapse(self: Box<Self>, removable: bool) -> Option<Box<dyn Layout>> {
+        let zero_sized = self.children.is_empty()
+            && !self.frozen
+            && self.relative_padding.is_none()
+            && self.padding == Padding::default()
+            && self.border == Border::default()
+            && !self.auto
+            && !self.justify_fill
+            && is_zero_sized(self.intrinsic_size);
+        if removable && zero_sized {
+            return None;
+        }
+        Some(self)
+    }
+
+    fn clone_with_ids(&self) -> Box<dyn Layout> {
+        let children = self
+            .children
+            .iter()
+            .map(|child| child.clone_with_ids())
+            .collect();
+        Box::new(self.clone_with(self.id, children))
     }
 
     fn iter(&self) -> LayoutIter<'_> {
         LayoutIter { stack: vec![self] }
     }
 
+    fn iter_lazy(&self) -> LazyLayoutIter<'_> {
+        LazyLayoutIter::new(self)
+    }
+
     fn solve_min_constraints(&mut self) -> (f32, f32) {
+        if self.frozen && self.solved {
+            return (self.constraints.min_width, self.constraints.min_height);
+        }
+
+        #[cfg(feature = "profiling")]
+        let _timer =
+            crate::profiling::timer("HorizontalLayout", crate::profiling::Pass::MinConstraints);
+
         let child_constraint_sum = self.compute_children_min_size();
-        match self.intrinsic_size.width {
+        match self.get_intrinsic_size().width {
             BoxSizing::Fixed(width) => {
                 self.constraints.min_width = width;
             }
-            BoxSizing::Flex(_) | BoxSizing::Shrink => {
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_) | BoxSizing::Shrink => {
                 self.constraints.min_width = child_constraint_sum.width;
             }
         }
 
-        match self.intrinsic_size.height {
+        match self.get_intrinsic_size().height {
             BoxSizing::Fixed(height) => {
                 self.constraints.min_height = height;
             }
-            BoxSizing::Flex(_) | BoxSizing::Shrink => {
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_) | BoxSizing::Shrink => {
                 self.constraints.min_height = child_constraint_sum.height;
             }
         }
 
+        let intrinsic_size = self.get_intrinsic_size();
+        apply_min_size(&mut self.constraints, intrinsic_size, self.min_size);
         (self.constraints.min_width, self.constraints.min_height)
     }
 
     fn solve_max_constraints(&mut self, _space: Size) {
-        // Sum up all the flex factors
-        // TODO: subtract max size from available width?
+        if self.frozen && self.solved {
+            return;
+        }
 
-        let flex_total = self.flex_total();
+        #[cfg(feature = "profiling")]
+        let _timer =
+            crate::profiling::timer("HorizontalLayout", crate::profiling::Pass::MaxConstraints);
 
-        let mut available_height;
-        match self.intrinsic_size.height {
-            BoxSizing::Shrink => available_height = self.constraints.min_height,
-            BoxSizing::Fixed(_) | BoxSizing::Flex(_) => {
-                available_height = self.constraints.max_height;
-                available_height -= self.padding.vertical_sum();
-            }
+        if let Some(padding) = self.relative_padding {
+            self.padding = padding.resolve(self.available_space());
         }
 
-        let mut available_width;
-        match self.intrinsic_size.width {
-            BoxSizing::Shrink => {
-                available_width = self.constraints.min_width;
-                available_width -= self.fixed_size_sum().width;
+        // The space inside the padding, which is the same extent used
+        // for the min size and for overflow
+        let mut content_width = match self.get_intrinsic_size().width {
+            BoxSizing::Shrink => self.constraints.min_width,
+            BoxSizing::Fixed(width) => width,
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_)
+                if self.main_axis_size == MainAxisSize::Min =>
+            {
+                self.constraints.min_width
             }
-            BoxSizing::Fixed(_) | BoxSizing::Flex(_) => {
-                available_width = self.constraints.max_width.unwrap_or_default();
-                available_width -= self.padding.horizontal_sum();
-                available_width -= self.fixed_size_sum().width;
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_) => flex_size(
+                self.constraints.min_width,
+                self.constraints.max_width.unwrap_or_default(),
+            ),
+        };
+        content_width -= self.insets().horizontal_sum();
+
+        let mut content_height = match self.get_intrinsic_size().height {
+            BoxSizing::Shrink => self.constraints.min_height,
+            BoxSizing::Fixed(height) => height,
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_) => {
+                flex_size(self.constraints.min_height, self.constraints.max_height)
             }
-        }
+        };
+        content_height -= self.insets().vertical_sum();
 
-        for child in &mut self.children {
+        let available_width = content_width - self.fixed_size_sum().width;
+        let available_height = content_height;
+        let cross_axis_alignment = self.cross_axis_alignment;
+
+        let sizing: Vec<_> = self
+            .children
+            .iter()
+            .map(|child| sharing_width(child.as_ref()))
+            .collect();
+        let min_sizes: Vec<_> = self
+            .children
+            .iter()
+            .map(|child| child.constraints().min_width)
+            .collect();
+        let shares = flex_shares(available_width, &sizing, &min_sizes, self.round_flex);
+
+        for (child, share) in self.children.iter_mut().zip(shares) {
             if child.constraints().max_width.is_none() {
-                match child.get_intrinsic_size().width {
-                    BoxSizing::Flex(factor) => {
-                        let grow_factor = factor as f32 / flex_total as f32;
-                        child.set_max_width(grow_factor * available_width);
+                match sharing_width(child.as_ref()) {
+                    BoxSizing::Flex(_) | BoxSizing::FlexF(_) => {
+                        // Never give a flex child less than its min size
+                        let width = share.max(child.constraints().min_width);
+                        child.set_max_width(width);
+                        // Shrink children are sized to their min width
+                        if child.get_intrinsic_size().width == BoxSizing::Shrink {
+                            child.set_min_width(width);
+                        }
                     }
                     BoxSizing::Fixed(width) => {
                         child.set_max_width(width);
@@ -385,8 +1205,8 @@ impl Layout for HorizontalLayout {
             }
 
             match child.get_intrinsic_size().height {
-                BoxSizing::Flex(_) => {
-                    child.set_max_height(available_height);
+                BoxSizing::Flex(_) | BoxSizing::FlexF(_) => {
+                    child.set_max_height(available_height.max(child.constraints().min_height));
                 }
                 BoxSizing::Fixed(height) => {
                     child.set_max_height(height);
@@ -396,6 +1216,18 @@ impl Layout for HorizontalLayout {
                 }
             }
 
+            let alignment = match child.get_cross_self() {
+                Some(alignment) => alignment,
+                None if self.stretch_cross_axis => AxisAlignment::Stretch,
+                None => cross_axis_alignment,
+            };
+            let fixed = matches!(child.get_intrinsic_size().height, BoxSizing::Fixed(_));
+            if alignment == AxisAlignment::Stretch && !fixed {
+                let height = content_height.max(child.constraints().min_height);
+                child.set_min_height(height);
+                child.set_max_height(height);
+            }
+
             // Pass the max size to the children to solve their max constraints
             let space = Size {
                 width: child.constraints().max_width.unwrap_or_default(),
@@ -407,9 +1239,28 @@ impl Layout for HorizontalLayout {
     }
 
     fn update_size(&mut self) {
-        match self.intrinsic_size.width {
-            BoxSizing::Flex(_) => {
-                self.size.width = self.constraints.max_width.unwrap_or_default();
+        if self.frozen && self.solved {
+            return;
+        }
+
+        self.solved = self.frozen;
+
+        match self.get_intrinsic_size().width {
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_)
+                if self.main_axis_size == MainAxisSize::Min =>
+            {
+                self.size.width = self.constraints.min_width;
+            }
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_) => {
+                self.size.width = flex_size(
+                    self.constraints.min_width,
+                    self.constraints.max_width.unwrap_or_default(),
+                );
+            }
+            BoxSizing::Shrink if self.justify_fill => {
+                let max_width = self.constraints.max_width.unwrap_or_default();
+                let max_width = flex_size(self.constraints.min_width, max_width);
+                self.size.width = self.constraints.min_width.max(max_width);
             }
             BoxSizing::Shrink => {
                 self.size.width = self.constraints.min_width;
@@ -419,9 +1270,10 @@ impl Layout for HorizontalLayout {
             }
         }
 
-        match self.intrinsic_size.height {
-            BoxSizing::Flex(_) => {
-                self.size.height = self.constraints.max_height;
+        match self.get_intrinsic_size().height {
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_) => {
+                self.size.height =
+                    flex_size(self.constraints.min_height, self.constraints.max_height);
             }
             BoxSizing::Shrink => {
                 self.size.height = self.constraints.min_height;
@@ -434,33 +1286,76 @@ impl Layout for HorizontalLayout {
         for child in &mut self.children {
             child.update_size();
         }
+
+        if !self.report_overflow {
+            return;
+        }
+
+        let extent = self.content_extent();
+        let main_axis_error = LayoutError::overflow(self.id, OverflowAxis::MainAxis);
+        let cross_axis_error = LayoutError::overflow(self.id, OverflowAxis::CrossAxis);
+
+        // Prevent duplicate errors
+        if !self.errors.contains(&main_axis_error) && overflows(extent.width, self.size.width) {
+            self.errors.push(main_axis_error);
+        }
+
+        if !self.errors.contains(&cross_axis_error) && overflows(extent.height, self.size.height) {
+            self.errors.push(cross_axis_error);
+        }
     }
 
     fn position_children(&mut self) {
+        #[cfg(feature = "profiling")]
+        let _timer = crate::profiling::timer("HorizontalLayout", crate::profiling::Pass::Position);
+
         match self.main_axis_alignment {
-            AxisAlignment::Start => self.align_main_axis_start(),
+            _ if self.justify_fill => self.align_main_axis_start(),
+            AxisAlignment::Start | AxisAlignment::Stretch => self.align_main_axis_start(),
             AxisAlignment::Center => self.align_main_axis_center(),
             AxisAlignment::End => self.align_main_axis_end(),
         }
 
-        match self.cross_axis_alignment {
-            AxisAlignment::Start => self.align_cross_axis_start(),
-            AxisAlignment::Center => self.align_cross_axis_center(),
-            AxisAlignment::End => self.align_cross_axis_end(),
-        }
+        self.align_cross_axis();
 
         for child in &mut self.children {
+            let x = child.position().x;
+            child.set_x(x + self.scroll_offset);
+
             if child.position().x > self.position.x + self.size.width {
                 self.errors.push(LayoutError::OutOfBounds {
                     parent_id: self.id,
                     child_id: child.id().to_owned(),
                 });
             }
-            child.position_children();
+            position_child(child.as_mut());
         }
     }
 }
 
+/// The sizing a child's width is shared out by. A shrink child with a
+/// flex descendant grows into the free space like a flex child, so that
+/// the descendant has space to fill.
+fn sharing_width(child: &dyn Layout) -> BoxSizing {
+    match child.get_intrinsic_size().width {
+        BoxSizing::Shrink if has_flex_width(child) => BoxSizing::Flex(1),
+        sizing => sizing,
+    }
+}
+
+/// Returns `true` if the layout has a flex width descendant that isn't
+/// behind a fixed width node.
+fn has_flex_width(layout: &dyn Layout) -> bool {
+    layout
+        .children()
+        .iter()
+        .any(|child| match child.get_intrinsic_size().width {
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_) => true,
+            BoxSizing::Shrink => has_flex_width(child.as_ref()),
+            BoxSizing::Fixed(_) => false,
+        })
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -548,6 +1443,68 @@ mod test {
         assert_eq!(layout.constraints.min_height, max_height);
     }
 
+    #[test]
+    fn overflow_agrees_with_min_width() {
+        let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+        let build = |width: f32| {
+            HorizontalLayout::new()
+                .intrinsic_size(IntrinsicSize::fixed(width, 50.0))
+                .padding(Padding::symmetric(0.0, 10.0))
+                .spacing(20)
+                .add_children([child.clone(), child.clone()])
+        };
+
+        let main_axis_error =
+            |layout: &HorizontalLayout| LayoutError::overflow(layout.id(), OverflowAxis::MainAxis);
+
+        let mut layout = build(140.0);
+        let errors = solve_layout(&mut layout, Size::unit(500.0));
+        assert!(errors.is_empty());
+
+        let mut layout = build(139.0);
+        let errors = solve_layout(&mut layout, Size::unit(500.0));
+        assert!(errors.contains(&main_axis_error(&layout)));
+    }
+
+    #[test]
+    fn empty_min_size_includes_padding() {
+        let mut layout = HorizontalLayout::new().padding(Padding::new(1.0, 2.0, 3.0, 4.0));
+        let errors = solve_layout(&mut layout, Size::unit(500.0));
+
+        assert!(errors.is_empty());
+        assert_eq!(layout.size(), Size::new(3.0, 7.0));
+    }
+
+    #[test]
+    fn collapse_spacing() {
+        let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(150.0, 150.0));
+        let mut layout = HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fixed(460.0, 150.0))
+            .spacing(40)
+            .collapsible_spacing(true)
+            .add_children([child.clone(), child.clone(), child]);
+
+        solve_layout(&mut layout, Size::unit(1000.0));
+
+        assert_eq!(layout.resolved_spacing(), 5.0);
+        assert_eq!(layout.children[0].position().x, 0.0);
+        assert_eq!(layout.children[1].position().x, 155.0);
+        assert_eq!(layout.children[2].position().x, 310.0);
+    }
+
+    #[test]
+    fn spacing_not_collapsed_by_default() {
+        let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(150.0, 150.0));
+        let mut layout = HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fixed(460.0, 150.0))
+            .spacing(40)
+            .add_children([child.clone(), child]);
+
+        solve_layout(&mut layout, Size::unit(1000.0));
+
+        assert_eq!(layout.resolved_spacing(), 40.0);
+    }
+
     #[test]
     fn align_main_axis_center_no_children() {
         let mut layout = HorizontalLayout::new();
@@ -1,21 +1,38 @@
-use crate::{Bounds, BoxConstraints, GlobalId, IntrinsicSize, LayoutError, Position, Size};
+use crate::{
+    AxisAlignment, Border, Bounds, BoxConstraints, BoxSizing, GlobalId, HitRegion, IntrinsicSize,
+    LayoutError, Padding, Position, Size,
+};
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 pub mod block;
+pub mod cached;
+pub mod custom;
 pub mod empty;
 pub mod horizontal;
+pub mod responsive;
+pub mod template;
 pub mod vertical;
 
 pub use block::BlockLayout;
+pub use cached::CachedLayout;
+pub use custom::CustomLayout;
 pub use empty::EmptyLayout;
 pub use horizontal::HorizontalLayout;
+pub use responsive::ResponsiveLayout;
+pub use template::Template;
 pub use vertical::VerticalLayout;
 
 /// Solve the final size and position of all the layout nodes. The
-/// `window_size` is the maximum available space for the root node.
+/// `window_size` is the maximum available space for the root node, either
+/// axis can be [`f32::INFINITY`] for content that is unbounded on that axis,
+/// nodes that flex on an infinite axis use their min size.
 ///
 /// This functions
-/// returns any layout errors such as overflow or out of bounds.
+/// returns any layout errors such as overflow or out of bounds. With the
+/// `constraint-checks` feature the solved tree is also checked for nodes
+/// that are larger than their constraints.
 ///
 /// # Example
 ///
@@ -31,6 +48,117 @@ pub use vertical::VerticalLayout;
 /// assert!(errors.is_empty());
 /// ```
 pub fn solve_layout(root: &mut dyn Layout, window_size: Size) -> Vec<LayoutError> {
+    let mut errors = vec![];
+    solve_layout_into(root, window_size, &mut errors);
+    errors
+}
+
+/// Solve the layout, the same as [`solve_layout`], but write the errors into
+/// a buffer that can be reused across solves. The buffer is cleared first.
+///
+/// # Example
+///
+/// ```
+/// use cascada::{solve_layout_into, EmptyLayout, HorizontalLayout, Size};
+///
+/// let mut errors = Vec::new();
+/// let mut root = HorizontalLayout::new().add_child(EmptyLayout::new());
+///
+/// solve_layout_into(&mut root, Size::unit(500.0), &mut errors);
+/// assert!(errors.is_empty());
+/// ```
+pub fn solve_layout_into(root: &mut dyn Layout, window_size: Size, errors: &mut Vec<LayoutError>) {
+    errors.clear();
+    solve_passes(root, window_size, false, errors);
+}
+
+/// The passes shared by every solve, which size and position the tree and
+/// collect its errors into `errors`. With `fill` any axis of the root that
+/// would shrink grows to the window instead.
+fn solve_passes(
+    root: &mut dyn Layout,
+    window_size: Size,
+    fill: bool,
+    errors: &mut Vec<LayoutError>,
+) {
+    restore_touch_targets(root);
+    pin_widths(root, &[]);
+    measure_heights(root, false);
+    solve_sizes(root, window_size, fill);
+    // A node that switched its children while solving the max constraints
+    // changed its min size after its parent had shared out the space, so
+    // the tree is solved again with the new children.
+    if root.children_switched() {
+        root.reset();
+        solve_sizes(root, window_size, fill);
+    }
+    // Width links need the solved width of the first node, so the tree is
+    // solved again with both nodes pinned to that width. This sizes the
+    // subtree of the second node, its siblings and the overflow of its
+    // parent with the linked width.
+    let pins = linked_widths(root);
+    if !pins.is_empty() {
+        root.reset();
+        pin_widths(root, &pins);
+        solve_sizes(root, window_size, fill);
+    }
+    // The min height of nodes whose height depends on their width is
+    // solved before their width is known, so they are measured with the
+    // solved width and the tree is solved again to fit the new heights.
+    if measure_heights(root, true) {
+        root.reset();
+        solve_sizes(root, window_size, fill);
+    }
+    root.position_children();
+
+    root.collect_errors_into(errors);
+    expand_touch_targets(root, errors);
+    #[cfg(feature = "constraint-checks")]
+    errors.extend(check_constraints(root));
+}
+
+/// The width that each node in a [width link](HorizontalLayout::link_width)
+/// is pinned to, which is the solved width of the first node.
+fn linked_widths(root: &dyn Layout) -> Vec<(GlobalId, f32)> {
+    let mut pins = vec![];
+    for layout in root.iter() {
+        for &(a, b) in layout.width_links() {
+            if let Some(width) = root.get(a).map(|a| a.size().width) {
+                pins.push((a, width));
+                pins.push((b, width));
+            }
+        }
+    }
+    pins
+}
+
+/// Pin the width of every node in `pins` and unpin the rest of the tree.
+fn pin_widths(layout: &mut dyn Layout, pins: &[(GlobalId, f32)]) {
+    let id = layout.id();
+    let width = pins
+        .iter()
+        .find(|(pinned, _)| *pinned == id)
+        .map(|(_, width)| *width);
+    layout.pin_width(width);
+    for child in layout.children_mut() {
+        pin_widths(child.as_mut(), pins);
+    }
+}
+
+/// Measure the height of every node whose height depends on its width, or
+/// clear the measured heights if `measure` is false. Returns `true` if
+/// there is such a node in the tree.
+fn measure_heights(layout: &mut dyn Layout, measure: bool) -> bool {
+    let mut found = layout.needs_width_for_height();
+    layout.measure_height(measure);
+    for child in layout.children_mut() {
+        found |= measure_heights(child.as_mut(), measure);
+    }
+    found
+}
+
+/// Solve the constraints and the size of every node in the tree.
+fn solve_sizes(root: &mut dyn Layout, window_size: Size, fill: bool) {
     if root.constraints().max_width.is_none() {
         root.set_max_width(window_size.width);
     }
@@ -38,18 +166,493 @@ pub fn solve_layout(root: &mut dyn Layout, window_size: Size) -> Vec<LayoutError
 
     // It's important that the min constraints are solved before the max constraints
     // because the min constraints are used in calculating max constraints.
-    let _ = root.solve_min_constraints();
+    solve_root_min_constraints(root, window_size, fill);
     root.solve_max_constraints(window_size);
     root.update_size();
-    root.position_children();
+}
+
+fn solve_root_min_constraints(root: &mut dyn Layout, window_size: Size, fill: bool) {
+    let (min_width, min_height) = root.solve_min_constraints();
+    if !fill {
+        return;
+    }
+
+    let intrinsic_size = root.get_intrinsic_size();
+    if intrinsic_size.width == BoxSizing::Shrink {
+        root.set_min_width(min_width.max(window_size.width));
+    }
+    if intrinsic_size.height == BoxSizing::Shrink {
+        root.set_min_height(min_height.max(window_size.height));
+    }
+}
+
+/// Check that every node in a solved tree fits in the max constraints of
+/// its parent. The root isn't checked, since it is allowed to be larger
+/// than the window.
+#[cfg(feature = "constraint-checks")]
+pub(crate) fn check_constraints(root: &dyn Layout) -> Vec<LayoutError> {
+    fn exceeds(layout: &dyn Layout, constraints: BoxConstraints) -> bool {
+        let size = layout.size();
+        let width = constraints
+            .max_width
+            .is_some_and(|max_width| overflows(size.width, max_width));
+        width || overflows(size.height, constraints.max_height)
+    }
+
+    // Collapsed subtrees have no space to fit in, so they aren't checked
+    fn check(layout: &dyn Layout, errors: &mut Vec<LayoutError>) {
+        if layout.is_collapsed() {
+            return;
+        }
+        for child in layout.children() {
+            if exceeds(child.as_ref(), layout.constraints()) {
+                errors.push(LayoutError::constraint_violation(child.id()));
+            }
+            check(child.as_ref(), errors);
+        }
+    }
+
+    let mut errors = vec![];
+    check(root, &mut errors);
+    errors
+}
+
+/// Grow every node with a [minimum touch target](EmptyLayout::min_touch_target)
+/// around its center. This runs once the whole tree has been positioned,
+/// so the growth doesn't move any siblings. Nodes that grow outside of
+/// their parent are reported as out of bounds.
+fn expand_touch_targets<L: Layout + ?Sized>(layout: &mut L, errors: &mut Vec<LayoutError>) {
+    layout.expand_touch_target(true);
+    let parent_id = layout.id();
+    let bounds = layout.bounds();
+    for child in layout.children_mut() {
+        let size = child.size();
+        expand_touch_targets(child.as_mut(), errors);
+        if child.size() != size && !bounds.contains(&child.bounds()) {
+            errors.push(LayoutError::OutOfBounds {
+                parent_id,
+                child_id: child.id(),
+            });
+        }
+    }
+}
+
+/// Shrink every node that grew to its touch target back to the size and
+/// position it was solved with, so the tree can be positioned again.
+fn restore_touch_targets<L: Layout + ?Sized>(layout: &mut L) {
+    layout.expand_touch_target(false);
+    for child in layout.children_mut() {
+        restore_touch_targets(child.as_mut());
+    }
+}
+
+/// Solve the layout and return the size of the root, discarding any
+/// errors. This is useful for measuring a tree before it is placed.
+///
+/// # Example
+///
+/// ```
+/// use cascada::{measure_layout, EmptyLayout, HorizontalLayout, IntrinsicSize, Size};
+///
+/// let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+/// let mut root = HorizontalLayout::new().add_children([child.clone(), child]);
+///
+/// assert_eq!(measure_layout(&mut root, Size::unit(500.0)), Size::new(100.0, 50.0));
+/// ```
+pub fn measure_layout(root: &mut dyn Layout, window_size: Size) -> Size {
+    let _ = solve_layout(root, window_size);
+    root.size()
+}
+
+/// Solve the layout with the root filling the window. Any axis of the
+/// root that would [shrink](BoxSizing::Shrink) instead grows to the
+/// `window_size`, or to the size of its content if that is larger. Use
+/// [`solve_layout`] for a root that shrinks to its content.
+///
+/// # Example
+///
+/// ```
+/// use cascada::{solve_layout_fill, EmptyLayout, HorizontalLayout, Layout, Size};
+///
+/// let mut root = HorizontalLayout::new().add_child(EmptyLayout::new());
+///
+/// solve_layout_fill(&mut root, Size::new(800.0, 600.0));
+/// assert_eq!(root.size(), Size::new(800.0, 600.0));
+/// ```
+pub fn solve_layout_fill(root: &mut dyn Layout, window_size: Size) -> Vec<LayoutError> {
+    let mut errors = vec![];
+    solve_passes(root, window_size, true, &mut errors);
+    errors
+}
+
+/// Solve the layout with every length multiplied by `scale`, such as
+/// the DPI scale factor of the display. Fixed sizes, padding, borders,
+/// spacing and max widths are all scaled, while the flex space is still
+/// split in proportion to the flex factors. The `window_size` is in
+/// scaled pixels.
+///
+/// The tree is solved in unscaled pixels and the final sizes and
+/// positions are then scaled and [snapped](snap_to_pixels) to whole
+/// scaled pixels, so fractional scales such as `1.5` don't leave edges
+/// between pixels. The [constraints](Layout::constraints) are left
+/// unscaled.
+///
+/// # Example
+///
+/// ```
+/// use cascada::{solve_layout_scaled, BlockLayout, EmptyLayout, IntrinsicSize, Layout, Padding, Size};
+///
+/// let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+/// let mut root = BlockLayout::new(child).padding(Padding::all(10.0));
+///
+/// solve_layout_scaled(&mut root, Size::unit(1000.0), 2.0);
+/// assert_eq!(root.size(), Size::unit(140.0));
+/// assert_eq!(root.child().size(), Size::unit(100.0));
+/// ```
+pub fn solve_layout_scaled(
+    root: &mut dyn Layout,
+    window_size: Size,
+    scale: f32,
+) -> Vec<LayoutError> {
+    let logical_size = Size::new(window_size.width / scale, window_size.height / scale);
+    let errors = solve_layout(root, logical_size);
+    scale_tree(root, scale);
+    snap_to_pixels(root, 1.0);
+    errors
+}
+
+fn scale_tree(root: &mut dyn Layout, scale: f32) {
+    let position = root.position();
+    let size = root.size();
+    root.set_position(Position::new(position.x * scale, position.y * scale));
+    root.set_size(Size::new(size.width * scale, size.height * scale));
+
+    for child in root.children_mut() {
+        scale_tree(child.as_mut(), scale);
+    }
+}
+
+/// Solve the layout and [snap](snap_to_pixels) every node to whole
+/// pixels, which avoids blurry edges from fractional positions.
+///
+/// # Example
+///
+/// ```
+/// use cascada::{solve_layout_snapped, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, Size};
+///
+/// let mut root = HorizontalLayout::new()
+///     .intrinsic_size(IntrinsicSize::fill())
+///     .add_children([
+///         EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()),
+///         EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()),
+///         EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()),
+///     ]);
+///
+/// solve_layout_snapped(&mut root, Size::unit(100.0));
+/// assert_eq!(root.children()[1].position().x, 33.0);
+/// assert_eq!(root.children()[1].size().width, 34.0);
+/// ```
+pub fn solve_layout_snapped(root: &mut dyn Layout, window_size: Size) -> Vec<LayoutError> {
+    let errors = solve_layout(root, window_size);
+    snap_to_pixels(root, 1.0);
+    errors
+}
+
+/// Round the edges of every node in a solved tree to the device pixel
+/// grid, where a logical pixel is made of `scale_factor` device pixels.
+///
+/// Each edge is rounded separately and the size is taken from the
+/// rounded edges, so nodes that shared an edge before snapping still
+/// share it afterwards.
+pub fn snap_to_pixels(root: &mut dyn Layout, scale_factor: f32) {
+    let snap = |value: f32| (value * scale_factor).round() / scale_factor;
+
+    let position = root.position();
+    let size = root.size();
+    let x = snap(position.x);
+    let y = snap(position.y);
+    root.set_position(Position::new(x, y));
+    root.set_size(Size::new(
+        snap(position.x + size.width) - x,
+        snap(position.y + size.height) - y,
+    ));
+
+    for child in root.children_mut() {
+        snap_to_pixels(child.as_mut(), scale_factor);
+    }
+}
+
+/// A snapshot of the scroll offset of a container, which can be restored
+/// after the container is solved again.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ScrollState {
+    /// The scroll offset along the main axis.
+    pub offset: f32,
+}
+
+/// Options for [`solve_layout_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SolveOptions {
+    /// The maximum number of nodes allowed in the layout tree. Larger
+    /// trees are not solved.
+    pub max_nodes: Option<usize>,
+    /// The visible area of the window. Children that are fully outside
+    /// of it are still positioned, but their own children are not.
+    pub viewport: Option<Bounds>,
+}
+
+/// Solve the layout with extra [`SolveOptions`].
+///
+/// If the tree has more than [`max_nodes`](SolveOptions::max_nodes) the layout
+/// isn't solved and a [`LayoutError::NodeLimitExceeded`] is returned, this is
+/// useful when the layout comes from an untrusted source.
+///
+/// # Example
+///
+/// ```
+/// use cascada::{solve_layout_with, EmptyLayout, HorizontalLayout, LayoutError, Size, SolveOptions};
+///
+/// let mut root = HorizontalLayout::new()
+///     .add_children([EmptyLayout::new(), EmptyLayout::new()]);
+///
+/// let options = SolveOptions {
+///     max_nodes: Some(2),
+///     ..Default::default()
+/// };
+/// let errors = solve_layout_with(&mut root, Size::unit(500.0), options);
+/// assert_eq!(errors, [LayoutError::node_limit_exceeded(2)]);
+/// ```
+pub fn solve_layout_with(
+    root: &mut dyn Layout,
+    window_size: Size,
+    options: SolveOptions,
+) -> Vec<LayoutError> {
+    let (errors, _) = solve_layout_culled(root, window_size, options);
+    errors
+}
+
+/// Solve the layout with extra [`SolveOptions`], the same as [`solve_layout_with`],
+/// and return the ids of the nodes that were culled because they were fully outside
+/// of the [`viewport`](SolveOptions::viewport). The descendants of a culled node
+/// are not positioned.
+///
+/// # Example
+///
+/// ```
+/// use cascada::{solve_layout_culled, Bounds, EmptyLayout, IntrinsicSize, Layout, Position};
+/// use cascada::{Size, SolveOptions, VerticalLayout};
+///
+/// let item = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 100.0));
+/// let mut root = VerticalLayout::new().add_children([item.clone(), item]);
+///
+/// let options = SolveOptions {
+///     viewport: Some(Bounds::new(Position::default(), Size::unit(100.0))),
+///     ..Default::default()
+/// };
+/// let (errors, culled) = solve_layout_culled(&mut root, Size::unit(500.0), options);
+/// assert!(errors.is_empty());
+/// assert_eq!(culled, [root.children()[1].id()]);
+/// ```
+pub fn solve_layout_culled(
+    root: &mut dyn Layout,
+    window_size: Size,
+    options: SolveOptions,
+) -> (Vec<LayoutError>, Vec<GlobalId>) {
+    if let Some(limit) = options.max_nodes {
+        // Stop counting as soon as the limit is passed
+        if root.iter().take(limit + 1).count() > limit {
+            return (vec![LayoutError::node_limit_exceeded(limit)], vec![]);
+        }
+    }
+
+    let Some(viewport) = options.viewport else {
+        return (solve_layout(root, window_size), vec![]);
+    };
+
+    let culling = CullingGuard::install(viewport);
+    let errors = solve_layout(root, window_size);
+    (errors, culling.culled())
+}
+
+thread_local! {
+    /// The viewport of the active [`solve_layout_culled`] call on this thread.
+    static CULLING: RefCell<Option<Culling>> = const { RefCell::new(None) };
+}
+
+struct Culling {
+    viewport: Bounds,
+    culled: Vec<GlobalId>,
+}
+
+/// Installs a viewport for culling and restores the previous one when
+/// dropped, so a solve that panics doesn't leave culling on for later
+/// solves on the same thread.
+struct CullingGuard {
+    previous: Option<Culling>,
+}
+
+impl CullingGuard {
+    fn install(viewport: Bounds) -> Self {
+        let previous = CULLING.replace(Some(Culling {
+            viewport,
+            culled: vec![],
+        }));
+        Self { previous }
+    }
+
+    /// Returns the ids of the nodes culled since the guard was installed.
+    fn culled(self) -> Vec<GlobalId> {
+        CULLING.with_borrow_mut(|culling| {
+            culling
+                .as_mut()
+                .map(|culling| std::mem::take(&mut culling.culled))
+                .unwrap_or_default()
+        })
+    }
+}
+
+impl Drop for CullingGuard {
+    fn drop(&mut self) {
+        CULLING.set(self.previous.take());
+    }
+}
+
+/// Position the children of a `child` that has already been positioned by its
+/// parent. When solving with a [`viewport`](SolveOptions::viewport) the children
+/// of a node that is fully outside of it are skipped.
+pub(crate) fn position_child(child: &mut dyn Layout) {
+    let culled = CULLING.with_borrow_mut(|culling| {
+        let Some(culling) = culling else { return false };
+        if culling.viewport.intersects(&child.bounds()) {
+            return false;
+        }
+        culling.culled.push(child.id());
+        true
+    });
+
+    if !culled {
+        child.position_children();
+    }
+}
+
+/// Solve the layout with a default [`BoxSizing`]. Any node marked as
+/// [`auto`](EmptyLayout::auto) that has an axis left as [`BoxSizing::Shrink`]
+/// will use the `default` sizing for that axis instead. Nodes that are not
+/// marked as auto keep their sizing.
+///
+/// # Example
+///
+/// ```
+/// use cascada::{solve_layout_with_defaults, BoxSizing, EmptyLayout, Layout, Size};
+///
+/// let mut root = EmptyLayout::new().auto();
+///
+/// solve_layout_with_defaults(&mut root, Size::unit(500.0), BoxSizing::Flex(1));
+/// assert_eq!(root.size(), Size::unit(500.0));
+/// ```
+pub fn solve_layout_with_defaults(
+    root: &mut dyn Layout,
+    window_size: Size,
+    default: BoxSizing,
+) -> Vec<LayoutError> {
+    root.set_default_sizing(default);
+    solve_layout(root, window_size)
+}
 
-    root.collect_errors()
+/// Solve the layout in a percentage of the window. The space available to
+/// the root is `percent` of the `window_size` on each axis, so a root that
+/// is [`fill`](IntrinsicSize::fill) sized takes up that percentage of the window.
+///
+/// # Example
+///
+/// ```
+/// use cascada::{solve_layout_percent, EmptyLayout, IntrinsicSize, Layout, Size};
+///
+/// let mut root = EmptyLayout::new()
+///     .intrinsic_size(IntrinsicSize::fill());
+///
+/// solve_layout_percent(&mut root, Size::new(1000.0, 500.0), 80.0);
+/// assert_eq!(root.size(), Size::new(800.0, 400.0));
+/// ```
+pub fn solve_layout_percent(
+    root: &mut dyn Layout,
+    window_size: Size,
+    percent: f32,
+) -> Vec<LayoutError> {
+    let fraction = percent.max(0.0) / 100.0;
+    let size = Size::new(window_size.width * fraction, window_size.height * fraction);
+    solve_layout(root, size)
+}
+
+/// Solve the layout inside the safe area of the window. The `insets`
+/// are removed from the `window_size` and the root node is offset
+/// by the top and left insets, so no node is placed in the unsafe region.
+///
+/// # Example
+///
+/// ```
+/// use cascada::{solve_layout_with_insets, EmptyLayout, IntrinsicSize, Layout, Padding, Size};
+///
+/// let mut root = EmptyLayout::new()
+///     .intrinsic_size(IntrinsicSize::fill());
+///
+/// solve_layout_with_insets(&mut root, Size::unit(500.0), Padding::new(0.0, 0.0, 20.0, 0.0));
+///
+/// assert_eq!(root.position().y, 20.0);
+/// assert_eq!(root.size().height, 480.0);
+/// ```
+pub fn solve_layout_with_insets(
+    root: &mut dyn Layout,
+    window_size: Size,
+    insets: Padding,
+) -> Vec<LayoutError> {
+    // Insets larger than the window leave no space rather than a negative one
+    let safe_size = Size::new(
+        (window_size.width - insets.horizontal_sum()).max(0.0),
+        (window_size.height - insets.vertical_sum()).max(0.0),
+    );
+    root.set_position(Position::new(insets.left, insets.top));
+    solve_layout(root, safe_size)
+}
+
+/// Solve the layout and return the [`Bounds`] of every node, keyed by
+/// its id, along with any layout errors. This is useful when the
+/// geometry is needed without holding on to the tree.
+///
+/// # Example
+///
+/// ```
+/// use cascada::{solve_to_map, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, Size};
+///
+/// let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+/// let id = child.id();
+/// let mut root = HorizontalLayout::new().add_child(child);
+///
+/// let (bounds, errors) = solve_to_map(&mut root, Size::unit(500.0));
+/// assert!(errors.is_empty());
+/// assert_eq!(bounds[&id].x, [0.0, 50.0]);
+/// ```
+pub fn solve_to_map(
+    root: &mut dyn Layout,
+    window_size: Size,
+) -> (HashMap<GlobalId, Bounds>, Vec<LayoutError>) {
+    let errors = solve_layout(root, window_size);
+    let bounds = root
+        .iter()
+        .map(|layout| (layout.id(), layout.bounds()))
+        .collect();
+    (bounds, errors)
 }
 
 /// A layout node.
-pub trait Layout: Debug + private::Sealed {
+pub trait Layout: Debug + Send + Sync + private::Sealed {
     fn label(&self) -> String;
 
+    /// The label used in compact debug output, which is the label set by
+    /// the user or a one letter tag of the node type, such as `"V"` for a
+    /// [`VerticalLayout`].
+    fn short_label(&self) -> String;
+
     /// Solve the minimum constraints of each [`Layout`] node recursively
     fn solve_min_constraints(&mut self) -> (f32, f32);
 
@@ -59,21 +662,194 @@ pub trait Layout: Debug + private::Sealed {
     /// Position the layout nodes after size calculations.
     fn position_children(&mut self);
 
+    /// Returns `true` if a node in the tree switched to different children
+    /// while solving the max constraints, such as a [`ResponsiveLayout`]
+    /// crossing its breakpoint. The min constraints its parent used are
+    /// then out of date, so the tree is solved again.
+    fn children_switched(&self) -> bool {
+        self.children()
+            .iter()
+            .any(|child| child.children_switched())
+    }
+
+    /// Grow the node around its center to its
+    /// [minimum touch target](EmptyLayout::min_touch_target), or shrink it
+    /// back to its solved size and position if `expand` is false. This
+    /// only applies to the node itself and not its children.
+    fn expand_touch_target(&mut self, _expand: bool) {}
+
+    /// The width links registered on this node, see
+    /// [`HorizontalLayout::link_width`].
+    fn width_links(&self) -> &[(GlobalId, GlobalId)] {
+        &[]
+    }
+
+    /// Returns `true` if the height of the node depends on its width, in
+    /// which case the tree is solved again once the width is known. See
+    /// [`CustomLayout::on_height_for_width`].
+    fn needs_width_for_height(&self) -> bool {
+        false
+    }
+
+    /// Measure the height of the node from its solved width, which is used
+    /// as its min height the next time it is solved. The measured height is
+    /// cleared if `measure` is false.
+    fn measure_height(&mut self, _measure: bool) {}
+
+    /// Size the node as if it had a fixed width, which is how
+    /// [width links](HorizontalLayout::link_width) are applied. `None`
+    /// goes back to the intrinsic width.
+    fn pin_width(&mut self, _width: Option<f32>) {}
+
     /// Update the size of every [`LayoutNode`] based on it's size and constraints.
     fn update_size(&mut self);
 
+    /// Reset the solved state of the tree, which is the size, position,
+    /// constraints and errors. The configuration of each node, such as
+    /// the intrinsic size, padding and max width are kept.
+    fn reset(&mut self);
+
+    /// Position the tree again using the current sizes, without solving
+    /// the constraints or sizes. This is useful after manually changing
+    /// the size of a node, such as when resizing a pane. Returns any
+    /// errors found while positioning.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, Size};
+    ///
+    /// let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+    /// let mut root = HorizontalLayout::new().add_children([child.clone(), child]);
+    /// solve_layout(&mut root, Size::unit(500.0));
+    ///
+    /// root.children_mut()[0].set_size(Size::new(100.0, 50.0));
+    /// root.reposition();
+    ///
+    /// assert_eq!(root.children()[1].position().x, 100.0);
+    /// ```
+    fn reposition(&mut self) -> Vec<LayoutError> {
+        restore_touch_targets(self);
+        self.position_children();
+        let mut errors = self.collect_errors();
+        expand_touch_targets(self, &mut errors);
+        errors
+    }
+
     /// Collect all the errors from the node tree.
     fn collect_errors(&mut self) -> Vec<LayoutError>;
 
+    /// Collect all the errors from the node tree into `errors`. By default
+    /// this collects them into a new `Vec` first, nodes override it to
+    /// write into `errors` directly.
+    fn collect_errors_into(&mut self, errors: &mut Vec<LayoutError>) {
+        errors.extend(self.collect_errors());
+    }
+
     /// Get the `id` of the [`Layout`]
     fn id(&self) -> GlobalId;
 
     /// Get the [`BoxConstraints`] of the [`Layout`]
     fn constraints(&self) -> BoxConstraints;
 
+    /// Get the space the [`Layout`] was given by its parent, which is its
+    /// max constraints. If the max width hasn't been set, the min width is
+    /// used instead, which is zero before the layout is solved.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, Size};
+    ///
+    /// let mut root = HorizontalLayout::new()
+    ///     .intrinsic_size(IntrinsicSize::fill())
+    ///     .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()));
+    /// solve_layout(&mut root, Size::new(500.0, 200.0));
+    ///
+    /// assert_eq!(root.children()[0].available_space(), Size::new(500.0, 200.0));
+    /// ```
+    fn available_space(&self) -> Size {
+        let constraints = self.constraints();
+        let width = constraints.max_width.unwrap_or(constraints.min_width);
+        Size::new(width, constraints.max_height)
+    }
+
     /// Get the [`IntrinsicSize`] of the [`Layout`]
     fn get_intrinsic_size(&self) -> IntrinsicSize;
 
+    /// Get the [`Padding`] of the [`Layout`]
+    fn get_padding(&self) -> Padding {
+        Padding::default()
+    }
+
+    /// Get the [`Border`] of the [`Layout`]
+    fn get_border(&self) -> Border {
+        Border::default()
+    }
+
+    /// Convert the [`Layout`] to an approximate [`taffy::Style`], so that
+    /// a tree can be mirrored into taffy for comparison. Containers become
+    /// flex rows or columns, use
+    /// [`to_taffy_child_style`](Layout::to_taffy_child_style) for the style
+    /// of a node inside one.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{HorizontalLayout, Layout};
+    ///
+    /// let style = HorizontalLayout::new().spacing(10).to_taffy_style();
+    /// assert_eq!(style.flex_direction, taffy::FlexDirection::Row);
+    /// ```
+    #[cfg(feature = "taffy-interop")]
+    fn to_taffy_style(&self) -> taffy::Style {
+        crate::taffy_interop::base_style(
+            self.get_intrinsic_size(),
+            self.get_padding(),
+            self.get_border(),
+            self.get_cross_self(),
+        )
+    }
+
+    /// Convert the [`Layout`] to an approximate [`taffy::Style`] as a child
+    /// of a flex container with the given `direction`. The flex factor on
+    /// the parent's main axis becomes `flex_grow` and flexing on the cross
+    /// axis becomes `align_self: Stretch`.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{EmptyLayout, IntrinsicSize, Layout};
+    ///
+    /// let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fill());
+    /// let style = child.to_taffy_child_style(taffy::FlexDirection::Row);
+    /// assert_eq!(style.flex_grow, 1.0);
+    /// ```
+    #[cfg(feature = "taffy-interop")]
+    fn to_taffy_child_style(&self, direction: taffy::FlexDirection) -> taffy::Style {
+        crate::taffy_interop::child_style(
+            self.to_taffy_style(),
+            self.get_intrinsic_size(),
+            direction,
+        )
+    }
+
+    /// Get the `z_index` of the [`Layout`]
+    fn get_z_index(&self) -> i32;
+
+    /// Get the opacity of the [`Layout`]
+    fn get_opacity(&self) -> f32;
+
+    /// Returns `true` if the [`Layout`] is hidden from the
+    /// [flattened](Layout::flatten) tree.
+    fn is_hidden(&self) -> bool;
+
+    /// Returns `true` if the [`Layout`] is collapsed, in which case it takes
+    /// up no space and the spacing around it is left out.
+    fn is_collapsed(&self) -> bool {
+        false
+    }
+
+    /// Get the alignment of the [`Layout`] on its parent's cross axis,
+    /// which overrides the parent's alignment.
+    fn get_cross_self(&self) -> Option<AxisAlignment>;
+
     /// Get the `Size` of the [`Layout`]
     fn size(&self) -> Size;
 
@@ -85,28 +861,596 @@ pub trait Layout: Debug + private::Sealed {
         Bounds::new(self.position(), self.size())
     }
 
+    /// Get the `Bounds` of the content of the [`Layout`], which is
+    /// the bounds inset by the padding and the border.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, BlockLayout, Bounds, EmptyLayout, Layout, Padding, Position, Size};
+    ///
+    /// let mut block = BlockLayout::new(EmptyLayout::new()).padding(Padding::all(10.0));
+    /// solve_layout(&mut block, Size::unit(500.0));
+    ///
+    /// let content = Bounds::new(Position::unit(10.0), Size::unit(0.0));
+    /// assert_eq!(block.content_bounds(), content);
+    /// ```
+    fn content_bounds(&self) -> Bounds {
+        self.bounds()
+    }
+
+    /// Find which [`HitRegion`] of the [`Layout`] a `position` is in.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, BlockLayout, EmptyLayout, HitRegion, IntrinsicSize};
+    /// use cascada::{Layout, Padding, Position, Size};
+    ///
+    /// let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+    /// let mut block = BlockLayout::new(child).padding(Padding::all(10.0));
+    /// solve_layout(&mut block, Size::unit(500.0));
+    ///
+    /// assert_eq!(block.hit_region(Position::unit(5.0)), HitRegion::Padding);
+    /// assert_eq!(block.hit_region(Position::unit(25.0)), HitRegion::Content);
+    /// ```
+    fn hit_region(&self, position: Position) -> HitRegion {
+        if self.content_bounds().within(&position) {
+            HitRegion::Content
+        } else if self.bounds().within(&position) {
+            HitRegion::Padding
+        } else {
+            HitRegion::Outside
+        }
+    }
+
+    /// Solve the [`Layout`] against `constraints` instead of a window size,
+    /// and return the resolved size. The min constraints are raised to at
+    /// least the content's min size, so a node that shrinks is never
+    /// smaller than its content, and fixed sizes are kept even if they are
+    /// outside of the constraints.
+    ///
+    /// Any errors are kept and can be taken with
+    /// [`collect_errors`](Layout::collect_errors).
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{BoxConstraints, EmptyLayout, IntrinsicSize, Layout, Size};
+    ///
+    /// let mut layout = EmptyLayout::new();
+    /// let size = layout.layout(BoxConstraints::tight(Size::new(100.0, 40.0)));
+    /// assert_eq!(size, Size::new(100.0, 40.0));
+    /// ```
+    fn layout(&mut self, constraints: BoxConstraints) -> Size {
+        let max_width = constraints.max_width.unwrap_or(constraints.min_width);
+        self.set_max_width(max_width);
+        self.set_max_height(constraints.max_height);
+
+        let (min_width, min_height) = self.solve_min_constraints();
+        self.set_min_width(min_width.max(constraints.min_width));
+        self.set_min_height(min_height.max(constraints.min_height));
+
+        self.solve_max_constraints(Size::new(max_width, constraints.max_height));
+        self.update_size();
+        self.position_children();
+        self.size()
+    }
+
+    /// Returns the ids of every pair of children whose
+    /// [`bounds`](Layout::bounds) overlap, which is useful for finding
+    /// alignment bugs. Children that only share an edge don't overlap.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, Size};
+    ///
+    /// let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+    /// let mut layout = HorizontalLayout::new().add_children([child.clone(), child]);
+    /// solve_layout(&mut layout, Size::unit(500.0));
+    ///
+    /// assert!(layout.overlapping_children().is_empty());
+    /// ```
+    fn overlapping_children(&self) -> Vec<(GlobalId, GlobalId)> {
+        let children = self.children();
+        let mut pairs = vec![];
+        for (i, a) in children.iter().enumerate() {
+            for b in &children[i + 1..] {
+                if a.bounds().intersects(&b.bounds()) {
+                    pairs.push((a.id(), b.id()));
+                }
+            }
+        }
+        pairs
+    }
+
+    /// A one line summary of the [`Layout`] in the form
+    /// `<label> id=<id> size=<w>x<h> pos=<x>x<y>`.
+    fn summary(&self) -> String {
+        format!(
+            "{} id={} size={} pos={}",
+            self.label(),
+            self.id(),
+            self.size(),
+            self.position()
+        )
+    }
+
     fn children(&self) -> &[Box<dyn Layout>];
 
+    /// Get mutable references to the children of the [`Layout`].
+    fn children_mut(&mut self) -> &mut [Box<dyn Layout>];
+
     fn set_max_width(&mut self, width: f32);
     fn set_max_height(&mut self, height: f32);
     fn set_min_width(&mut self, width: f32);
     fn set_min_height(&mut self, height: f32);
 
+    /// Set the min and max constraints at once. The max width is only set
+    /// if it is `Some`, otherwise the current max width is kept.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{BoxConstraints, EmptyLayout, Layout, Size};
+    ///
+    /// let mut layout = EmptyLayout::new();
+    /// layout.set_constraints(BoxConstraints::loose(Size::new(100.0, 200.0)));
+    /// assert_eq!(layout.constraints().max_width, Some(100.0));
+    /// ```
+    fn set_constraints(&mut self, constraints: BoxConstraints) {
+        if let Some(max_width) = constraints.max_width {
+            self.set_max_width(max_width);
+        }
+        self.set_max_height(constraints.max_height);
+        self.set_min_width(constraints.min_width);
+        self.set_min_height(constraints.min_height);
+    }
+
+    /// Set the default [`BoxSizing`] used by [`auto`](EmptyLayout::auto) sized
+    /// nodes in the tree.
+    fn set_default_sizing(&mut self, sizing: BoxSizing);
+
     fn set_position(&mut self, position: Position) {
         self.set_x(position.x);
         self.set_y(position.y);
     }
 
+    /// Set the `Size` of the [`Layout`], this is overwritten the next time
+    /// the layout is solved.
+    fn set_size(&mut self, size: Size);
+
     fn set_x(&mut self, x: f32);
     fn set_y(&mut self, y: f32);
 
+    /// Remove redundant nodes from the tree below this node, without
+    /// changing the solved size and position of the remaining nodes.
+    ///
+    /// A [`BlockLayout`] that has no padding, border or styling, and
+    /// shrinks around a child that doesn't flex, is replaced by its child.
+    /// Children that always have a size of zero are removed from
+    /// containers without spacing. The removed nodes, and their ids, are
+    /// no longer part of the tree. The tree should be solved again
+    /// afterwards.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{BlockLayout, EmptyLayout, HorizontalLayout, Layout};
+    ///
+    /// let mut layout = HorizontalLayout::new()
+    ///     .add_child(BlockLayout::new(BlockLayout::new(HorizontalLayout::new())));
+    ///
+    /// layout.simplify();
+    /// assert_eq!(layout.iter().count(), 2);
+    /// ```
+    fn simplify(&mut self);
+
+    /// Returns the node that takes the place of this node when its parent
+    /// is simplified, or `None` if the node is `removable` and always has
+    /// a size of zero.
+    #[doc(hidden)]
+    fn collapse(self: Box<Self>, removable: bool) -> Option<Box<dyn Layout>>;
+
+    /// Clone the [`Layout`] and all of its children into a new tree,
+    /// every node in the new tree is given a new [`GlobalId`].
+    fn clone_boxed(&self) -> Box<dyn Layout>;
+
+    /// Clone the [`Layout`] and all of its children into a new tree,
+    /// keeping the [`GlobalId`] of every node.
+    ///
+    /// Since the ids are the same, a node in the copy can't be told
+    /// apart from the original by its id, so avoid storing both trees
+    /// in the same lookup.
+    fn clone_with_ids(&self) -> Box<dyn Layout>;
+
+    /// Move the [`Layout`] into a `Box<dyn Layout>`.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{EmptyLayout, HorizontalLayout, Layout};
+    ///
+    /// let children = vec![EmptyLayout::new().boxed(), HorizontalLayout::new().boxed()];
+    /// let layout = HorizontalLayout::new().add_boxed_children(children);
+    /// assert_eq!(layout.children().len(), 2);
+    /// ```
+    fn boxed(self) -> Box<dyn Layout>
+    where
+        Self: Sized + 'static,
+    {
+        Box::new(self)
+    }
+
     /// Iterate over the layout tree.
     fn iter(&self) -> LayoutIter<'_>;
 
+    /// Iterate over the layout tree in the same order as
+    /// [`iter`](Layout::iter), without copying every child into the
+    /// iterator. This keeps memory use down for very wide trees.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{EmptyLayout, HorizontalLayout, Layout};
+    ///
+    /// let layout = HorizontalLayout::new()
+    ///     .add_children([EmptyLayout::new(), EmptyLayout::new()]);
+    /// assert_eq!(layout.iter_lazy().count(), 3);
+    /// ```
+    fn iter_lazy(&self) -> LazyLayoutIter<'_>;
+
+    /// Flatten the layout tree into a list of [`FlatNode`]s in paint order.
+    /// Each node holds everything needed to draw and hit-test it without
+    /// holding on to the tree.
+    ///
+    /// Parents come before their children and siblings are ordered by their
+    /// `z_index`, siblings with the same `z_index` keep the same order as
+    /// [`iter`](Layout::iter). Hidden nodes and their children are skipped,
+    /// if the root is hidden the list is empty.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{solve_layout, EmptyLayout, HorizontalLayout, Layout, Size};
+    ///
+    /// let mut root = HorizontalLayout::new().add_child(EmptyLayout::new());
+    /// solve_layout(&mut root, Size::unit(500.0));
+    ///
+    /// let nodes = root.flatten();
+    /// assert_eq!(nodes[1].depth, 1);
+    /// assert_eq!(nodes[1].parent, Some(root.id()));
+    /// ```
+    fn flatten(&self) -> Vec<FlatNode> {
+        if self.is_hidden() || self.is_collapsed() {
+            return vec![];
+        }
+
+        let mut nodes = vec![FlatNode {
+            id: self.id(),
+            label: self.label(),
+            bounds: self.bounds(),
+            opacity: self.get_opacity(),
+            depth: 0,
+            parent: None,
+        }];
+
+        let mut stack: Vec<(&dyn Layout, usize, GlobalId)> = vec![];
+        let children = paint_order(self.children()).into_iter().rev();
+        stack.extend(children.map(|child| (child, 1, self.id())));
+
+        while let Some((layout, depth, parent)) = stack.pop() {
+            nodes.push(FlatNode {
+                id: layout.id(),
+                label: layout.label(),
+                bounds: layout.bounds(),
+                opacity: layout.get_opacity(),
+                depth,
+                parent: Some(parent),
+            });
+
+            let children = paint_order(layout.children()).into_iter().rev();
+            stack.extend(children.map(|child| (child, depth + 1, layout.id())));
+        }
+
+        nodes
+    }
+
     /// Get a [`Layout`] by it's `id`.
     fn get(&self, id: GlobalId) -> Option<&dyn Layout> {
         self.iter().find(|&layout| layout.id() == id)
     }
+
+    /// Get the ids that appear more than once in the tree, in the order
+    /// that they are first repeated. Nodes that share an id can't be told
+    /// apart by [`get`](Layout::get), so this should be empty for a
+    /// healthy tree.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{EmptyLayout, HorizontalLayout, Layout};
+    ///
+    /// let layout = HorizontalLayout::new()
+    ///     .add_children([EmptyLayout::new(), EmptyLayout::new()]);
+    ///
+    /// assert!(layout.duplicate_ids().is_empty());
+    /// ```
+    fn duplicate_ids(&self) -> Vec<GlobalId> {
+        let mut counts: HashMap<GlobalId, usize> = HashMap::new();
+        let mut duplicates = vec![];
+        for layout in self.iter() {
+            let count = counts.entry(layout.id()).or_default();
+            *count += 1;
+            if *count == 2 {
+                duplicates.push(layout.id());
+            }
+        }
+        duplicates
+    }
+
+    /// Get the ids of the ancestors of a node, starting from its parent
+    /// up to and including the root. This is empty if the node is the
+    /// root or isn't in the tree.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{BlockLayout, EmptyLayout, Layout};
+    ///
+    /// let child = EmptyLayout::new();
+    /// let id = child.id();
+    /// let root = BlockLayout::new(child);
+    ///
+    /// assert_eq!(root.ancestors(id), [root.id()]);
+    /// ```
+    fn ancestors(&self, id: GlobalId) -> Vec<GlobalId> {
+        // The stack holds each node along with the path from its parent to the root
+        let mut stack: Vec<(&dyn Layout, Vec<GlobalId>)> = self
+            .children()
+            .iter()
+            .map(|child| (child.as_ref(), vec![self.id()]))
+            .collect();
+
+        while let Some((layout, path)) = stack.pop() {
+            if layout.id() == id {
+                return path;
+            }
+
+            for child in layout.children() {
+                let mut path = path.clone();
+                path.insert(0, layout.id());
+                stack.push((child.as_ref(), path));
+            }
+        }
+
+        vec![]
+    }
+
+    /// Get the id of the sibling after a node, within the node's parent.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{EmptyLayout, HorizontalLayout, Layout};
+    ///
+    /// let first = EmptyLayout::new();
+    /// let second = EmptyLayout::new();
+    /// let (first_id, second_id) = (first.id(), second.id());
+    /// let root = HorizontalLayout::new().add_children([first, second]);
+    ///
+    /// assert_eq!(root.next_sibling(first_id), Some(second_id));
+    /// assert_eq!(root.next_sibling(second_id), None);
+    /// ```
+    fn next_sibling(&self, id: GlobalId) -> Option<GlobalId> {
+        let siblings = find_siblings(self.children(), id)?;
+        let index = siblings.iter().position(|child| child.id() == id)?;
+        siblings.get(index + 1).map(|sibling| sibling.id())
+    }
+
+    /// Get the id of the sibling before a node, within the node's parent.
+    fn prev_sibling(&self, id: GlobalId) -> Option<GlobalId> {
+        let siblings = find_siblings(self.children(), id)?;
+        let index = siblings.iter().position(|child| child.id() == id)?;
+        let index = index.checked_sub(1)?;
+        siblings.get(index).map(|sibling| sibling.id())
+    }
+
+    /// Get the index of a node within its parent's children. This is `None`
+    /// for the root and for nodes that aren't in the tree.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{EmptyLayout, HorizontalLayout, Layout};
+    ///
+    /// let child = EmptyLayout::new();
+    /// let id = child.id();
+    /// let root = HorizontalLayout::new().add_children([EmptyLayout::new(), child]);
+    ///
+    /// assert_eq!(root.index_in_parent(id), Some(1));
+    /// assert_eq!(root.index_in_parent(root.id()), None);
+    /// ```
+    fn index_in_parent(&self, id: GlobalId) -> Option<usize> {
+        let siblings = find_siblings(self.children(), id)?;
+        siblings.iter().position(|child| child.id() == id)
+    }
+}
+
+/// Split the available space between the flex children in proportion to
+/// their flex factor, given the main axis sizing and min size of every
+/// child. Children that don't flex get no space.
+///
+/// A flex child is never given less than its min size, if its share would
+/// be smaller it gets its min size and the rest of the space is split
+/// between the other flex children.
+///
+/// When `round` is set each share is rounded down to a whole pixel, and the
+/// remaining pixels are given to the first flex children, so the shares
+/// add up to exactly the available space.
+pub(crate) fn flex_shares(
+    available: f32,
+    sizing: &[BoxSizing],
+    min_sizes: &[f32],
+    round: bool,
+) -> Vec<f32> {
+    // Flexing into infinite space is undefined, so the flex children
+    // are only given their min size
+    if !available.is_finite() {
+        return sizing
+            .iter()
+            .zip(min_sizes)
+            .map(|(sizing, min_size)| {
+                if sizing.flex_factor() > 0.0 {
+                    *min_size
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+    }
+
+    let factor = |sizing: &BoxSizing| sizing.flex_factor();
+
+    // Children that flex but would get less than their min size
+    let mut frozen = vec![false; sizing.len()];
+    let (remaining, flex_total) = loop {
+        let mut remaining = available;
+        let mut flex_total = 0.0;
+        for ((sizing, min_size), frozen) in sizing.iter().zip(min_sizes).zip(&frozen) {
+            if *frozen {
+                remaining -= min_size;
+            } else {
+                flex_total += factor(sizing);
+            }
+        }
+
+        let mut changed = false;
+        for ((sizing, min_size), frozen) in sizing.iter().zip(min_sizes).zip(&mut frozen) {
+            let share = factor(sizing) / flex_total * remaining;
+            if !*frozen && factor(sizing) > 0.0 && share < *min_size {
+                *frozen = true;
+                changed = true;
+            }
+        }
+
+        if !changed {
+            break (remaining, flex_total);
+        }
+    };
+
+    let share = |sizing: &BoxSizing, available: f32| factor(sizing) / flex_total * available;
+    let mut shares: Vec<f32> = sizing
+        .iter()
+        .zip(min_sizes)
+        .zip(&frozen)
+        .map(|((sizing, min_size), frozen)| match sizing {
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_) if *frozen => *min_size,
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_) if round => {
+                share(sizing, remaining.max(0.0).round()).floor()
+            }
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_) => share(sizing, remaining),
+            _ => 0.0,
+        })
+        .collect();
+    if !round {
+        return shares;
+    }
+
+    let mut remainder = remaining.max(0.0).round()
+        - shares
+            .iter()
+            .zip(&frozen)
+            .filter(|(_, frozen)| !**frozen)
+            .map(|(share, _)| share)
+            .sum::<f32>();
+    for ((share, sizing), frozen) in shares.iter_mut().zip(sizing).zip(&frozen) {
+        if remainder < 1.0 {
+            break;
+        }
+        if let BoxSizing::Flex(_) | BoxSizing::FlexF(_) = sizing
+            && !frozen
+        {
+            *share += 1.0;
+            remainder -= 1.0;
+        }
+    }
+    shares
+}
+
+/// The size of an axis that flexes, which fills the `max` size. An axis
+/// with an infinite max size, such as a window that is unbounded on that
+/// axis, falls back to the `min` size instead.
+pub(crate) fn flex_size(min: f32, max: f32) -> f32 {
+    if max.is_finite() { max } else { min }
+}
+
+/// Raise the min constraints on the axes that [shrink](BoxSizing::Shrink)
+/// to at least `min_size`.
+pub(crate) fn apply_min_size(
+    constraints: &mut BoxConstraints,
+    intrinsic_size: IntrinsicSize,
+    min_size: Size,
+) {
+    if intrinsic_size.width == BoxSizing::Shrink {
+        constraints.min_width = constraints.min_width.max(min_size.width);
+    }
+    if intrinsic_size.height == BoxSizing::Shrink {
+        constraints.min_height = constraints.min_height.max(min_size.height);
+    }
+}
+
+/// Returns `true` if the intrinsic size is always zero, without any
+/// padding or children.
+pub(crate) fn is_zero_sized(intrinsic_size: IntrinsicSize) -> bool {
+    let zero = |sizing: BoxSizing| match sizing {
+        BoxSizing::Shrink => true,
+        BoxSizing::Fixed(size) => size == 0.0,
+        BoxSizing::Flex(_) | BoxSizing::FlexF(_) => false,
+    };
+    zero(intrinsic_size.width) && zero(intrinsic_size.height)
+}
+
+/// Point width links at the ids of a clone, where `new` is a clone of
+/// `old` with new ids. Links to nodes outside of `old` are kept.
+pub(crate) fn remap_links(
+    links: &[(GlobalId, GlobalId)],
+    old: &dyn Layout,
+    new: &dyn Layout,
+) -> Vec<(GlobalId, GlobalId)> {
+    let ids: HashMap<_, _> = old
+        .iter()
+        .map(|layout| layout.id())
+        .zip(new.iter().map(|layout| layout.id()))
+        .collect();
+    let new_id = |id: GlobalId| ids.get(&id).copied().unwrap_or(id);
+    links.iter().map(|&(a, b)| (new_id(a), new_id(b))).collect()
+}
+
+/// The number of gaps between the children, spacing is only added between
+/// consecutive children that aren't [collapsed](Layout::is_collapsed).
+pub(crate) fn gap_count(children: &[Box<dyn Layout>]) -> usize {
+    let visible = children
+        .iter()
+        .filter(|child| !child.is_collapsed())
+        .count();
+    visible.saturating_sub(1)
+}
+
+/// Returns `true` if there is a gap before the `child`, which is when it
+/// isn't collapsed and comes after another child that isn't collapsed.
+/// `after_visible` tracks whether such a child has been seen so far.
+pub(crate) fn gap_before(child: &dyn Layout, after_visible: &mut bool) -> bool {
+    if child.is_collapsed() {
+        return false;
+    }
+    std::mem::replace(after_visible, true)
+}
+
+/// Returns `true` if the content extent is larger than the size, the
+/// extent and size are summed in different orders so a small rounding
+/// error is allowed.
+pub(crate) fn overflows(extent: f32, size: f32) -> bool {
+    extent - size > size.abs().max(1.0) * 1e-5
+}
+
+/// Find the children of the parent of the node with the `id`.
+fn find_siblings(children: &[Box<dyn Layout>], id: GlobalId) -> Option<&[Box<dyn Layout>]> {
+    if children.iter().any(|child| child.id() == id) {
+        return Some(children);
+    }
+
+    children
+        .iter()
+        .find_map(|child| find_siblings(child.children(), id))
 }
 
 mod private {
@@ -116,6 +1460,35 @@ mod private {
     impl Sealed for super::BlockLayout {}
     impl Sealed for super::HorizontalLayout {}
     impl Sealed for super::VerticalLayout {}
+    impl Sealed for super::ResponsiveLayout {}
+    impl Sealed for super::CustomLayout {}
+    impl Sealed for super::CachedLayout {}
+}
+
+/// Sort the visible children by their `z_index`, keeping the tree order for ties.
+fn paint_order(children: &[Box<dyn Layout>]) -> Vec<&dyn Layout> {
+    let mut children: Vec<&dyn Layout> = children
+        .iter()
+        .map(|child| child.as_ref())
+        .filter(|child| !child.is_hidden() && !child.is_collapsed())
+        .collect();
+    children.sort_by_key(|child| child.get_z_index());
+    children
+}
+
+/// A single node of a [flattened](Layout::flatten) layout tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlatNode {
+    pub id: GlobalId,
+    pub label: String,
+    pub bounds: Bounds,
+    /// The opacity of the node, this isn't multiplied by the opacity
+    /// of its ancestors.
+    pub opacity: f32,
+    /// The number of ancestors of the node, the root has a depth of `0`.
+    pub depth: usize,
+    /// The `id` of the parent node, this is `None` for the root.
+    pub parent: Option<GlobalId>,
 }
 
 /// An `Iterator` over the layout tree.
@@ -138,9 +1511,290 @@ impl<'a> Iterator for LayoutIter<'a> {
     }
 }
 
+/// A depth first iterator over a layout tree, which keeps an iterator
+/// over the children of each level instead of a stack of nodes. Created
+/// with [`Layout::iter_lazy`].
+pub struct LazyLayoutIter<'a> {
+    root: Option<&'a dyn Layout>,
+    levels: Vec<std::slice::Iter<'a, Box<dyn Layout>>>,
+}
+
+impl<'a> LazyLayoutIter<'a> {
+    pub fn new(root: &'a dyn Layout) -> Self {
+        Self {
+            root: Some(root),
+            levels: vec![],
+        }
+    }
+}
+
+impl<'a> Iterator for LazyLayoutIter<'a> {
+    type Item = &'a dyn Layout;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(root) = self.root.take() {
+            self.levels.push(root.children().iter());
+            return Some(root);
+        }
+
+        loop {
+            let level = self.levels.last_mut()?;
+            match level.next() {
+                Some(child) => {
+                    self.levels.push(child.children().iter());
+                    return Some(child.as_ref());
+                }
+                None => {
+                    self.levels.pop();
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::error::OverflowAxis;
+
+    #[test]
+    fn layout_with_tight_and_loose_constraints() {
+        let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 20.0));
+        let mut layout = HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fill())
+            .add_child(child);
+
+        let size = layout.layout(BoxConstraints::tight(Size::new(200.0, 100.0)));
+        assert_eq!(size, Size::new(200.0, 100.0));
+
+        layout.reset();
+        let size = layout.layout(BoxConstraints::loose(Size::new(300.0, 150.0)));
+        assert_eq!(size, Size::new(300.0, 150.0));
+        assert_eq!(layout.constraints().min_width, 50.0);
+
+        let mut shrink = HorizontalLayout::new()
+            .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 20.0)));
+        assert_eq!(
+            shrink.layout(BoxConstraints::loose(Size::unit(300.0))),
+            Size::new(50.0, 20.0)
+        );
+        shrink.reset();
+        assert_eq!(
+            shrink.layout(BoxConstraints::tight(Size::unit(80.0))),
+            Size::unit(80.0)
+        );
+    }
+
+    #[test]
+    fn report_overlapping_children() {
+        let child = || EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+        let mut root = HorizontalLayout::new().add_children([child(), child(), child()]);
+        solve_layout(&mut root, Size::unit(500.0));
+        assert!(root.overlapping_children().is_empty());
+
+        root.children_mut()[2].set_x(80.0);
+
+        let children = root.children();
+        let pairs = root.overlapping_children();
+        assert_eq!(pairs, [(children[1].id(), children[2].id())]);
+    }
+
+    #[test]
+    fn set_constraints_directly() {
+        let constraints = BoxConstraints {
+            max_width: Some(300.0),
+            max_height: 200.0,
+            min_width: 50.0,
+            min_height: 20.0,
+        };
+        let mut nodes: Vec<Box<dyn Layout>> = vec![
+            EmptyLayout::new().boxed(),
+            HorizontalLayout::new().boxed(),
+            CachedLayout::new(VerticalLayout::new()).boxed(),
+        ];
+
+        for node in &mut nodes {
+            node.set_constraints(constraints);
+            assert_eq!(node.constraints(), constraints);
+        }
+    }
+
+    #[test]
+    fn available_space_of_flex_child() {
+        let mut root = HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fixed(600.0, 100.0))
+            .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 50.0)))
+            .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize {
+                width: BoxSizing::Flex(1),
+                height: BoxSizing::Flex(1),
+            }))
+            .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize {
+                width: BoxSizing::Flex(4),
+                height: BoxSizing::Shrink,
+            }));
+
+        solve_layout(&mut root, Size::unit(1000.0));
+
+        let children = root.children();
+        assert_eq!(children[1].available_space(), Size::new(100.0, 100.0));
+        assert_eq!(children[2].available_space(), Size::new(400.0, 0.0));
+        assert_eq!(children[2].size(), Size::new(400.0, 0.0));
+    }
+
+    #[test]
+    fn lazy_iter_has_same_order() {
+        let row = || {
+            HorizontalLayout::new()
+                .add_children([EmptyLayout::new(), EmptyLayout::new()])
+                .add_child(VerticalLayout::new().add_child(EmptyLayout::new()))
+        };
+        let root = VerticalLayout::new()
+            .add_child(row())
+            .add_child(EmptyLayout::new())
+            .add_child(BlockLayout::new(row()))
+            .add_child(VerticalLayout::new());
+
+        let ids: Vec<_> = root.iter().map(|layout| layout.id()).collect();
+        let lazy_ids: Vec<_> = root.iter_lazy().map(|layout| layout.id()).collect();
+        assert_eq!(ids.len(), 14);
+        assert_eq!(ids, lazy_ids);
+    }
+
+    #[test]
+    #[cfg(feature = "constraint-checks")]
+    fn fixed_child_larger_than_fixed_parent() {
+        let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(300.0, 50.0));
+        let child_id = child.id();
+        let parent = HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fixed(100.0, 100.0))
+            .report_overflow(false)
+            .add_child(child);
+        let mut root = HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fill())
+            .add_child(parent);
+
+        let errors = solve_layout(&mut root, Size::unit(500.0));
+
+        assert_eq!(errors, [LayoutError::constraint_violation(child_id)]);
+    }
+
+    #[test]
+    #[cfg(feature = "constraint-checks")]
+    fn fill_checks_constraints() {
+        let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(300.0, 50.0));
+        let child_id = child.id();
+        let parent = HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fixed(100.0, 100.0))
+            .report_overflow(false)
+            .add_child(child);
+        let mut root = HorizontalLayout::new().add_child(parent);
+
+        let errors = solve_layout_fill(&mut root, Size::unit(500.0));
+
+        assert_eq!(errors, [LayoutError::constraint_violation(child_id)]);
+    }
+
+    #[test]
+    fn simplify_keeps_solved_layout() {
+        let fixed = || EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(40.0, 30.0));
+        let row = HorizontalLayout::new()
+            .add_child(fixed())
+            .add_child(EmptyLayout::new())
+            .add_child(VerticalLayout::new())
+            .add_child(fixed());
+        let mut root = VerticalLayout::new()
+            .intrinsic_size(IntrinsicSize::fill())
+            .padding(Padding::all(10.0))
+            .main_axis_alignment(AxisAlignment::Center)
+            .add_child(BlockLayout::new(BlockLayout::new(row)))
+            .add_child(BlockLayout::new(fixed()).padding(Padding::all(5.0)))
+            .add_child(BlockLayout::new(
+                EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()),
+            ));
+
+        solve_layout(&mut root, Size::unit(500.0));
+        let before: HashMap<_, _> = root.iter().map(|node| (node.id(), node.bounds())).collect();
+
+        root.simplify();
+        root.reset();
+        solve_layout(&mut root, Size::unit(500.0));
+
+        // The two plain blocks and two empty nodes in the row are removed
+        assert_eq!(root.iter().count(), before.len() - 4);
+        for node in root.iter() {
+            assert_eq!(before[&node.id()], node.bounds(), "{}", node.summary());
+        }
+    }
+
+    #[test]
+    fn rounded_flex_shares_sum_to_available_space() {
+        let sizing = [BoxSizing::Flex(1); 3];
+        assert_eq!(
+            flex_shares(1000.0, &sizing, &[0.0; 3], true),
+            [334.0, 333.0, 333.0]
+        );
+
+        let sizing = [
+            BoxSizing::Flex(1),
+            BoxSizing::Fixed(20.0),
+            BoxSizing::Flex(2),
+        ];
+        let shares = flex_shares(500.0, &sizing, &[0.0, 20.0, 0.0], true);
+        assert_eq!(shares, [167.0, 0.0, 333.0]);
+    }
+
+    #[test]
+    fn flex_shares_respect_min_size() {
+        let sizing = [BoxSizing::Flex(1); 3];
+        let shares = flex_shares(300.0, &sizing, &[0.0, 150.0, 0.0], false);
+        assert_eq!(shares, [75.0, 150.0, 75.0]);
+
+        let shares = flex_shares(100.0, &sizing, &[50.0, 150.0, 0.0], true);
+        assert_eq!(shares, [50.0, 150.0, 0.0]);
+    }
+
+    #[test]
+    fn fill_root_only_with_fill_variant() {
+        let child = || EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+        let window = Size::new(800.0, 600.0);
+
+        let mut root = VerticalLayout::new().add_child(child());
+        solve_layout(&mut root, window);
+        assert_eq!(root.size(), Size::unit(50.0));
+
+        let mut root = VerticalLayout::new().add_child(child());
+        let errors = solve_layout_fill(&mut root, window);
+        assert!(errors.is_empty());
+        assert_eq!(root.size(), window);
+    }
+
+    #[test]
+    fn fill_keeps_fixed_axis() {
+        let mut root = EmptyLayout::new().intrinsic_size(IntrinsicSize {
+            width: BoxSizing::Fixed(100.0),
+            ..Default::default()
+        });
+
+        solve_layout_fill(&mut root, Size::new(800.0, 600.0));
+        assert_eq!(root.size(), Size::new(100.0, 600.0));
+    }
+
+    #[test]
+    fn solve_to_map_covers_all_nodes() {
+        let child = || EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+        let mut root = VerticalLayout::new()
+            .padding(Padding::all(10.0))
+            .add_child(BlockLayout::new(child()).padding(Padding::all(5.0)))
+            .add_child(HorizontalLayout::new().add_children([child(), child()]));
+
+        let (map, errors) = solve_to_map(&mut root, Size::unit(500.0));
+
+        assert!(errors.is_empty());
+        assert_eq!(map.len(), root.iter().count());
+        for layout in root.iter() {
+            assert_eq!(map[&layout.id()], layout.bounds());
+        }
+    }
 
     #[test]
     fn root_max_width() {
@@ -151,4 +1805,330 @@ mod test {
         solve_layout(&mut layout, Size::unit(200.0));
         assert_eq!(layout.size().width, 20.0);
     }
+
+    #[test]
+    fn reset_solved_state() {
+        let child = EmptyLayout::new()
+            .max_width(50.0)
+            .intrinsic_size(IntrinsicSize::fill());
+        let mut layout = BlockLayout::new(child)
+            .padding(Padding::all(10.0))
+            .intrinsic_size(IntrinsicSize::fill());
+
+        solve_layout(&mut layout, Size::unit(200.0));
+        layout.reset();
+
+        let child = layout.child();
+        assert_eq!(layout.size(), Size::default());
+        assert_eq!(child.size(), Size::default());
+        assert_eq!(child.position(), Position::default());
+        assert_eq!(layout.constraints(), BoxConstraints::default());
+        assert_eq!(child.constraints().max_width, Some(50.0));
+        assert_eq!(layout.get_intrinsic_size(), IntrinsicSize::fill());
+        assert_eq!(child.get_intrinsic_size(), IntrinsicSize::fill());
+    }
+
+    #[test]
+    fn clone_with_ids_keeps_ids() {
+        let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(20.0, 20.0));
+        let id = child.id();
+        let mut layout = VerticalLayout::new()
+            .add_child(child)
+            .add_child(EmptyLayout::new());
+        solve_layout(&mut layout, Size::unit(200.0));
+
+        let copy = layout.clone_with_ids();
+        assert_eq!(copy.id(), layout.id());
+        let child = copy.get(id).unwrap();
+        assert_eq!(child.size(), Size::unit(20.0));
+        for (original, copy) in layout.iter().zip(copy.iter()) {
+            assert_eq!(original.id(), copy.id());
+        }
+    }
+
+    #[test]
+    fn node_summary() {
+        let mut layout = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(20.0, 50.5));
+        layout.set_position(Position::new(10.0, 5.0));
+        solve_layout(&mut layout, Size::unit(200.0));
+
+        let summary = format!("EmptyLayout id={} size=20x50.5 pos=10x5", layout.id());
+        assert_eq!(layout.summary(), summary);
+    }
+
+    #[test]
+    fn detect_duplicate_ids() {
+        let a = EmptyLayout::new();
+        let b = EmptyLayout::new();
+        let layout = VerticalLayout::new()
+            .add_child(a.clone())
+            .add_child(HorizontalLayout::new().add_children([b.clone(), a.clone()]))
+            .add_children([b.clone(), a.clone(), EmptyLayout::new()]);
+
+        assert_eq!(layout.duplicate_ids(), [a.id(), b.id()]);
+    }
+
+    #[test]
+    fn measure_shrink_container() {
+        let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(40.0, 80.0));
+        let mut layout = VerticalLayout::new()
+            .padding(Padding::all(10.0))
+            .spacing(20)
+            .add_children([child.clone(), child]);
+
+        let size = measure_layout(&mut layout, Size::unit(1000.0));
+        assert_eq!(size, Size::new(60.0, 200.0));
+    }
+
+    #[test]
+    fn reuse_errors_buffer() {
+        let mut errors = Vec::new();
+        let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(200.0, 50.0));
+        let mut overflowing = HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fixed(100.0, 100.0))
+            .add_child(child);
+        solve_layout_into(&mut overflowing, Size::unit(500.0), &mut errors);
+        assert_eq!(
+            errors,
+            [LayoutError::overflow(
+                overflowing.id(),
+                OverflowAxis::MainAxis
+            )]
+        );
+
+        let mut layout = HorizontalLayout::new().add_child(EmptyLayout::new());
+        solve_layout_into(&mut layout, Size::unit(500.0), &mut errors);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn index_of_third_child() {
+        let third = EmptyLayout::new();
+        let id = third.id();
+        let row =
+            HorizontalLayout::new().add_children([EmptyLayout::new(), EmptyLayout::new(), third]);
+        let root = VerticalLayout::new()
+            .add_child(EmptyLayout::new())
+            .add_child(row);
+
+        assert_eq!(root.index_in_parent(id), Some(2));
+        assert_eq!(root.index_in_parent(GlobalId::new()), None);
+    }
+
+    #[test]
+    fn node_limit_exceeded() {
+        let mut layout = VerticalLayout::new().intrinsic_size(IntrinsicSize::fill());
+        for _ in 0..49 {
+            layout = layout.add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()));
+        }
+        assert_eq!(layout.iter().count(), 50);
+
+        let options = SolveOptions {
+            max_nodes: Some(10),
+            ..Default::default()
+        };
+        let errors = solve_layout_with(&mut layout, Size::unit(500.0), options);
+
+        assert_eq!(errors, [LayoutError::node_limit_exceeded(10)]);
+        assert_eq!(layout.size(), Size::default());
+    }
+
+    #[test]
+    fn node_limit_not_exceeded() {
+        let mut layout = VerticalLayout::new().add_child(EmptyLayout::new());
+        let options = SolveOptions {
+            max_nodes: Some(2),
+            ..Default::default()
+        };
+        let errors = solve_layout_with(&mut layout, Size::unit(500.0), options);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn flatten_tree() {
+        let leaf = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(20.0, 20.0));
+        let leaf_id = leaf.id();
+        let block = BlockLayout::new(leaf);
+        let block_id = block.id();
+        let mut layout = HorizontalLayout::new()
+            .add_child(EmptyLayout::new())
+            .add_child(block);
+        solve_layout(&mut layout, Size::unit(200.0));
+
+        let nodes = layout.flatten();
+        assert_eq!(nodes.len(), layout.iter().count());
+        for (node, layout) in nodes.iter().zip(layout.iter()) {
+            assert_eq!(node.id, layout.id());
+            assert_eq!(node.bounds, layout.bounds());
+        }
+
+        assert_eq!(nodes[0].depth, 0);
+        assert_eq!(nodes[0].parent, None);
+        assert_eq!(nodes[2].depth, 1);
+        assert_eq!(nodes[3].id, leaf_id);
+        assert_eq!(nodes[3].depth, 2);
+        assert_eq!(nodes[3].parent, Some(block_id));
+    }
+
+    #[test]
+    fn flatten_in_z_index_order() {
+        let first = EmptyLayout::new().z_index(2);
+        let second = EmptyLayout::new();
+        let third = EmptyLayout::new().z_index(-1);
+        let fourth = EmptyLayout::new();
+        let ids = [third.id(), second.id(), fourth.id(), first.id()];
+        let mut layout = HorizontalLayout::new().add_children([first, second, third, fourth]);
+        solve_layout(&mut layout, Size::unit(200.0));
+
+        let nodes: Vec<GlobalId> = layout.flatten()[1..].iter().map(|node| node.id).collect();
+        assert_eq!(nodes, ids);
+    }
+
+    #[test]
+    fn flatten_skips_hidden_subtree() {
+        let leaf = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(20.0, 20.0));
+        let leaf_id = leaf.id();
+        let block = BlockLayout::new(leaf).hidden(true);
+        let block_id = block.id();
+        let mut layout = HorizontalLayout::new()
+            .add_child(EmptyLayout::new().opacity(0.5))
+            .add_child(block);
+        solve_layout(&mut layout, Size::unit(200.0));
+
+        let nodes = layout.flatten();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[1].opacity, 0.5);
+        assert!(
+            nodes
+                .iter()
+                .all(|node| node.id != block_id && node.id != leaf_id)
+        );
+
+        assert!(layout.get(block_id).is_some());
+        assert_eq!(layout.get(leaf_id).unwrap().size(), Size::unit(20.0));
+    }
+
+    #[test]
+    fn ancestor_chain() {
+        let leaf = EmptyLayout::new();
+        let leaf_id = leaf.id();
+        let block = BlockLayout::new(leaf);
+        let block_id = block.id();
+        let row = HorizontalLayout::new()
+            .add_child(EmptyLayout::new())
+            .add_child(block);
+        let row_id = row.id();
+        let layout = VerticalLayout::new().add_child(row);
+
+        assert_eq!(layout.ancestors(leaf_id), [block_id, row_id, layout.id()]);
+        assert_eq!(layout.ancestors(row_id), [layout.id()]);
+        assert!(layout.ancestors(layout.id()).is_empty());
+        assert!(layout.ancestors(GlobalId::new()).is_empty());
+    }
+
+    #[test]
+    fn sibling_navigation() {
+        let children = [EmptyLayout::new(), EmptyLayout::new(), EmptyLayout::new()];
+        let ids: Vec<GlobalId> = children.iter().map(|child| child.id()).collect();
+        let layout = HorizontalLayout::new().add_children(children);
+
+        assert_eq!(layout.next_sibling(ids[0]), Some(ids[1]));
+        assert_eq!(layout.next_sibling(ids[1]), Some(ids[2]));
+        assert_eq!(layout.next_sibling(ids[2]), None);
+        assert_eq!(layout.prev_sibling(ids[2]), Some(ids[1]));
+        assert_eq!(layout.prev_sibling(ids[1]), Some(ids[0]));
+        assert_eq!(layout.prev_sibling(ids[0]), None);
+    }
+
+    #[test]
+    fn nested_sibling_navigation() {
+        let first = EmptyLayout::new();
+        let second = EmptyLayout::new();
+        let (first_id, second_id) = (first.id(), second.id());
+        let column = VerticalLayout::new().add_children([first, second]);
+        let layout = HorizontalLayout::new()
+            .add_child(EmptyLayout::new())
+            .add_child(column);
+
+        assert_eq!(layout.next_sibling(first_id), Some(second_id));
+        assert_eq!(layout.prev_sibling(second_id), Some(first_id));
+        assert_eq!(layout.next_sibling(layout.id()), None);
+        assert_eq!(layout.prev_sibling(GlobalId::new()), None);
+    }
+
+    #[test]
+    fn percent_of_window_root() {
+        let mut layout = HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fill())
+            .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()));
+
+        solve_layout_percent(&mut layout, Size::unit(1000.0), 80.0);
+
+        assert_eq!(layout.size(), Size::unit(800.0));
+        assert_eq!(layout.children()[0].size(), Size::unit(800.0));
+    }
+
+    #[test]
+    fn max_width_unset_until_solved() {
+        let mut layout = HorizontalLayout::new().intrinsic_size(IntrinsicSize::fill());
+        assert_eq!(layout.constraints().max_width, None);
+
+        solve_layout(&mut layout, Size::unit(200.0));
+        assert_eq!(layout.constraints().max_width, Some(200.0));
+    }
+
+    #[test]
+    fn auto_nodes_use_default_sizing() {
+        let auto = EmptyLayout::new().auto();
+        let fixed = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+        let mut layout =
+            HorizontalLayout::new()
+                .auto()
+                .add_children([auto.clone(), auto, fixed.auto()]);
+
+        solve_layout_with_defaults(&mut layout, Size::new(450.0, 200.0), BoxSizing::Flex(1));
+
+        assert_eq!(layout.size(), Size::new(450.0, 200.0));
+        assert_eq!(layout.children()[0].size(), Size::new(200.0, 200.0));
+        assert_eq!(layout.children()[1].size(), Size::new(200.0, 200.0));
+        assert_eq!(layout.children()[2].size(), Size::new(50.0, 50.0));
+    }
+
+    #[test]
+    fn default_sizing_ignored_without_auto() {
+        let mut layout = HorizontalLayout::new().add_child(EmptyLayout::new());
+
+        solve_layout_with_defaults(&mut layout, Size::unit(500.0), BoxSizing::Flex(1));
+
+        assert_eq!(layout.size(), Size::default());
+        assert_eq!(layout.children()[0].size(), Size::default());
+    }
+
+    #[test]
+    fn insets_offset_root_content() {
+        let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fill());
+        let mut layout = VerticalLayout::new()
+            .intrinsic_size(IntrinsicSize::fill())
+            .add_child(child);
+
+        let insets = Padding::new(0.0, 0.0, 20.0, 0.0);
+        solve_layout_with_insets(&mut layout, Size::unit(200.0), insets);
+
+        assert_eq!(layout.position(), Position::new(0.0, 20.0));
+        assert_eq!(layout.size(), Size::new(200.0, 180.0));
+        assert_eq!(layout.children()[0].position().y, 20.0);
+        assert_eq!(layout.children()[0].size().height, 180.0);
+    }
+
+    #[test]
+    fn insets_larger_than_window() {
+        let mut layout = EmptyLayout::new().intrinsic_size(IntrinsicSize::fill());
+
+        let insets = Padding::new(150.0, 100.0, 20.0, 0.0);
+        let errors = solve_layout_with_insets(&mut layout, Size::unit(200.0), insets);
+
+        assert!(errors.is_empty());
+        assert_eq!(layout.position(), Position::new(150.0, 20.0));
+        assert_eq!(layout.size(), Size::new(0.0, 180.0));
+    }
 }
@@ -0,0 +1,491 @@
+use super::position_child;
+use crate::{
+    AxisAlignment, BoxConstraints, BoxSizing, GlobalId, IntrinsicSize, Layout, LayoutError,
+    LayoutIter, LazyLayoutIter, Position, Size,
+};
+
+/// A [`Layout`] that switches between two arrangements of its content
+/// depending on the width available to it, for example a column on narrow
+/// windows and a row on wide windows.
+///
+/// The `wide` layout is used when the available width is at least the
+/// `breakpoint`, otherwise the `narrow` layout is used. Only the layout in
+/// use is part of the tree, it is given all the space of the responsive
+/// layout and the responsive layout has the same size and position.
+///
+/// The responsive layout always flexes on the main axis, so that its
+/// parent gives it the available width.
+///
+/// # Example
+/// ```
+/// use cascada::{solve_layout, EmptyLayout, HorizontalLayout, Layout, ResponsiveLayout, Size, VerticalLayout};
+///
+/// let mut layout = ResponsiveLayout::new(
+///     600.0,
+///     VerticalLayout::new().add_children([EmptyLayout::new(), EmptyLayout::new()]),
+///     HorizontalLayout::new().add_children([EmptyLayout::new(), EmptyLayout::new()]),
+/// );
+///
+/// solve_layout(&mut layout, Size::new(400.0, 800.0));
+/// assert_eq!(layout.children()[0].label(), "VerticalLayout");
+/// ```
+#[derive(Debug)]
+pub struct ResponsiveLayout {
+    id: GlobalId,
+    size: Size,
+    position: Position,
+    constraints: BoxConstraints,
+    breakpoint: f32,
+    /// The narrow and wide layouts.
+    children: [Box<dyn Layout>; 2],
+    wide: bool,
+    /// Whether the layout switched while solving the max constraints.
+    switched: bool,
+    label: Option<String>,
+    z_index: i32,
+    opacity: f32,
+    hidden: bool,
+    /// The width given by a width link, which replaces the intrinsic width.
+    pinned_width: Option<f32>,
+    /// Whether the solved layout is kept after the first solve.
+    frozen: bool,
+    /// Whether a frozen layout has been solved.
+    solved: bool,
+    cross_self: Option<AxisAlignment>,
+}
+
+impl ResponsiveLayout {
+    /// Create a new responsive layout, that uses the `wide` layout when
+    /// there is at least `breakpoint` width available.
+    pub fn new(
+        breakpoint: f32,
+        narrow: impl Layout + 'static,
+        wide: impl Layout + 'static,
+    ) -> Self {
+        Self::from_boxed(breakpoint, Box::new(narrow), Box::new(wide))
+    }
+
+    /// Create a new responsive layout from boxed layouts.
+    pub fn from_boxed(breakpoint: f32, narrow: Box<dyn Layout>, wide: Box<dyn Layout>) -> Self {
+        Self {
+            id: GlobalId::new(),
+            size: Size::default(),
+            position: Position::default(),
+            constraints: BoxConstraints::default(),
+            breakpoint,
+            children: [narrow, wide],
+            wide: false,
+            switched: false,
+            label: None,
+            z_index: 0,
+            opacity: 1.0,
+            hidden: false,
+            pinned_width: None,
+            frozen: false,
+            solved: false,
+            cross_self: None,
+        }
+    }
+
+    pub fn set_id(mut self, id: GlobalId) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// Set the paint order of the node relative to its siblings in the
+    /// [flattened](Layout::flatten) tree, nodes with a higher `z_index`
+    /// are drawn later. This does not affect the layout.
+    pub fn z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+
+    /// Set the opacity of the node, which is clamped between `0.0` and `1.0`.
+    /// This is passed on to the [flattened](Layout::flatten) tree and does
+    /// not affect the layout.
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Hide the node and its children from the [flattened](Layout::flatten)
+    /// tree. Hidden nodes still take up space in the layout.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Keep the solved layout of this node and its children after the
+    /// first solve. Later solves skip the subtree and only move it to its
+    /// new position, and resetting the tree doesn't clear it.
+    pub fn frozen(mut self, frozen: bool) -> Self {
+        self.frozen = frozen;
+        self.solved = false;
+        self
+    }
+
+    /// Align this node on its parent's cross axis, overriding the parent's
+    /// cross axis alignment.
+    pub fn cross_self(mut self, alignment: AxisAlignment) -> Self {
+        self.cross_self = Some(alignment);
+        self
+    }
+
+    /// Returns `true` if the wide layout is being used.
+    pub fn is_wide(&self) -> bool {
+        self.wide
+    }
+
+    fn active(&self) -> &dyn Layout {
+        self.children[self.wide as usize].as_ref()
+    }
+
+    fn active_mut(&mut self) -> &mut Box<dyn Layout> {
+        &mut self.children[self.wide as usize]
+    }
+
+    fn clone_with(&self, id: GlobalId, children: [Box<dyn Layout>; 2]) -> Self {
+        Self {
+            id,
+            size: self.size,
+            position: self.position,
+            constraints: self.constraints,
+            breakpoint: self.breakpoint,
+            children,
+            wide: self.wide,
+            switched: self.switched,
+            label: self.label.clone(),
+            z_index: self.z_index,
+            opacity: self.opacity,
+            hidden: self.hidden,
+            pinned_width: self.pinned_width,
+            frozen: self.frozen,
+            solved: self.solved,
+            cross_self: self.cross_self,
+        }
+    }
+}
+
+impl Layout for ResponsiveLayout {
+    fn label(&self) -> String {
+        self.label.clone().unwrap_or("ResponsiveLayout".to_string())
+    }
+
+    fn short_label(&self) -> String {
+        self.label.clone().unwrap_or("R".to_string())
+    }
+
+    fn solve_min_constraints(&mut self) -> (f32, f32) {
+        if self.frozen && self.solved {
+            return (self.constraints.min_width, self.constraints.min_height);
+        }
+
+        #[cfg(feature = "profiling")]
+        let _timer =
+            crate::profiling::timer("ResponsiveLayout", crate::profiling::Pass::MinConstraints);
+
+        // The root's max width is known before the min constraints are
+        // solved, for other nodes the previous layout is kept until the
+        // max constraints are solved.
+        self.switched = false;
+        if let Some(max_width) = self.constraints.max_width {
+            self.wide = max_width >= self.breakpoint;
+        }
+
+        let (min_width, min_height) = self.active_mut().solve_min_constraints();
+        self.constraints.min_width = min_width;
+        self.constraints.min_height = min_height;
+        (min_width, min_height)
+    }
+
+    fn solve_max_constraints(&mut self, space: Size) {
+        if self.frozen && self.solved {
+            return;
+        }
+
+        #[cfg(feature = "profiling")]
+        let _timer =
+            crate::profiling::timer("ResponsiveLayout", crate::profiling::Pass::MaxConstraints);
+
+        let max_width = self.constraints.max_width.unwrap_or_default();
+        let wide = max_width >= self.breakpoint;
+        if wide != self.wide {
+            self.wide = wide;
+            self.switched = true;
+            let (min_width, min_height) = self.active_mut().solve_min_constraints();
+            self.constraints.min_width = min_width;
+            self.constraints.min_height = min_height;
+        }
+
+        let max_height = self.constraints.max_height;
+        let active = self.active_mut();
+        if active.constraints().max_width.is_none() {
+            match active.get_intrinsic_size().width {
+                BoxSizing::Flex(_) | BoxSizing::FlexF(_) => active.set_max_width(max_width),
+                BoxSizing::Fixed(width) => active.set_max_width(width),
+                BoxSizing::Shrink => {
+                    let min_width = active.constraints().min_width;
+                    active.set_max_width(min_width);
+                }
+            }
+        }
+        match active.get_intrinsic_size().height {
+            BoxSizing::Flex(_) | BoxSizing::FlexF(_) => active.set_max_height(max_height),
+            BoxSizing::Fixed(height) => active.set_max_height(height),
+            BoxSizing::Shrink => {
+                let min_height = active.constraints().min_height;
+                active.set_max_height(min_height);
+            }
+        }
+
+        active.solve_max_constraints(space);
+    }
+
+    fn position_children(&mut self) {
+        #[cfg(feature = "profiling")]
+        let _timer = crate::profiling::timer("ResponsiveLayout", crate::profiling::Pass::Position);
+
+        let position = self.position;
+        let active = self.active_mut();
+        active.set_position(position);
+        position_child(active.as_mut());
+    }
+
+    fn update_size(&mut self) {
+        if self.frozen && self.solved {
+            return;
+        }
+
+        self.solved = self.frozen;
+
+        self.active_mut().update_size();
+        self.size = self.active().size();
+    }
+
+    fn reset(&mut self) {
+        if self.frozen && self.solved {
+            return;
+        }
+
+        self.size = Size::default();
+        self.position = Position::default();
+        self.constraints = BoxConstraints::default();
+        for child in &mut self.children {
+            child.reset();
+        }
+    }
+
+    fn children_switched(&self) -> bool {
+        self.switched || self.active().children_switched()
+    }
+
+    fn collect_errors(&mut self) -> Vec<LayoutError> {
+        self.active_mut().collect_errors()
+    }
+
+    fn collect_errors_into(&mut self, errors: &mut Vec<LayoutError>) {
+        self.active_mut().collect_errors_into(errors);
+    }
+
+    fn id(&self) -> GlobalId {
+        self.id
+    }
+
+    fn constraints(&self) -> BoxConstraints {
+        self.constraints
+    }
+
+    fn get_intrinsic_size(&self) -> IntrinsicSize {
+        IntrinsicSize {
+            width: self
+                .pinned_width
+                .map_or(BoxSizing::Flex(1), BoxSizing::Fixed),
+            height: self.active().get_intrinsic_size().height,
+        }
+    }
+
+    fn get_z_index(&self) -> i32 {
+        self.z_index
+    }
+
+    fn get_opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    fn get_cross_self(&self) -> Option<AxisAlignment> {
+        self.cross_self
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn position(&self) -> Position {
+        self.position
+    }
+
+    fn children(&self) -> &[Box<dyn Layout>] {
+        let index = self.wide as usize;
+        &self.children[index..=index]
+    }
+
+    fn children_mut(&mut self) -> &mut [Box<dyn Layout>] {
+        let index = self.wide as usize;
+        &mut self.children[index..=index]
+    }
+
+    fn set_max_width(&mut self, width: f32) {
+        self.constraints.max_width = Some(width);
+    }
+
+    fn set_max_height(&mut self, height: f32) {
+        self.constraints.max_height = height;
+    }
+
+    fn set_min_width(&mut self, width: f32) {
+        self.constraints.min_width = width;
+    }
+
+    fn set_min_height(&mut self, height: f32) {
+        self.constraints.min_height = height;
+    }
+
+    fn pin_width(&mut self, width: Option<f32>) {
+        self.pinned_width = width;
+    }
+
+    fn set_default_sizing(&mut self, sizing: BoxSizing) {
+        for child in &mut self.children {
+            child.set_default_sizing(sizing);
+        }
+    }
+
+    fn set_size(&mut self, size: Size) {
+        self.size = size;
+    }
+
+    fn set_x(&mut self, x: f32) {
+        self.position.x = x;
+    }
+
+    fn set_y(&mut self, y: f32) {
+        self.position.y = y;
+    }
+
+    fn simplify(&mut self) {
+        for child in &mut self.children {
+            child.simplify();
+        }
+    }
+
+    fn collapse(self: Box<Self>, _removable: bool) -> Option<Box<dyn Layout>> {
+        Some(self)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn Layout> {
+        let children = [
+            self.children[0].clone_boxed(),
+            self.children[1].clone_boxed(),
+        ];
+        Box::new(self.clone_with(GlobalId::new(), children))
+    }
+
+    fn clone_with_ids(&self) -> Box<dyn Layout> {
+        let children = [
+            self.children[0].clone_with_ids(),
+            self.children[1].clone_with_ids(),
+        ];
+        Box::new(self.clone_with(self.id, children))
+    }
+
+    fn iter(&self) -> LayoutIter<'_> {
+        LayoutIter { stack: vec![self] }
+    }
+
+    fn iter_lazy(&self) -> LazyLayoutIter<'_> {
+        LazyLayoutIter::new(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{EmptyLayout, HorizontalLayout, VerticalLayout, solve_layout};
+
+    fn responsive() -> ResponsiveLayout {
+        let item = || EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 50.0));
+        ResponsiveLayout::new(
+            600.0,
+            VerticalLayout::new().add_children([item(), item()]),
+            HorizontalLayout::new().add_children([item(), item()]),
+        )
+    }
+
+    #[test]
+    fn narrow_below_breakpoint() {
+        let mut root = HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fixed(400.0, 400.0))
+            .add_child(responsive());
+
+        solve_layout(&mut root, Size::unit(1000.0));
+
+        let layout = &root.children()[0];
+        assert_eq!(layout.children()[0].label(), "VerticalLayout");
+        assert_eq!(layout.size(), Size::new(100.0, 100.0));
+    }
+
+    #[test]
+    fn wide_above_breakpoint() {
+        let mut root = HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fixed(800.0, 400.0))
+            .add_child(responsive());
+
+        solve_layout(&mut root, Size::unit(1000.0));
+
+        let layout = &root.children()[0];
+        assert_eq!(layout.children()[0].label(), "HorizontalLayout");
+        assert_eq!(layout.size(), Size::new(200.0, 50.0));
+        assert_eq!(layout.children()[0].children()[1].position().x, 100.0);
+    }
+
+    #[test]
+    fn root_switches_on_window_width() {
+        let mut layout = responsive();
+        solve_layout(&mut layout, Size::new(700.0, 500.0));
+        assert!(layout.is_wide());
+
+        layout.reset();
+        solve_layout(&mut layout, Size::new(500.0, 500.0));
+        assert!(!layout.is_wide());
+        assert_eq!(layout.iter().count(), 4);
+    }
+
+    #[test]
+    fn parent_shrinks_to_switched_variant() {
+        // The narrow variant is 100 tall and the wide one is 50 tall, the
+        // column has to shrink to the wide one after it switches.
+        let mut root = VerticalLayout::new()
+            .intrinsic_size(IntrinsicSize {
+                width: BoxSizing::Flex(1),
+                height: BoxSizing::Shrink,
+            })
+            .add_child(responsive());
+
+        let errors = solve_layout(&mut root, Size::unit(1000.0));
+
+        let layout = &root.children()[0];
+        assert_eq!(layout.children()[0].label(), "HorizontalLayout");
+        assert_eq!(layout.size().height, 50.0);
+        assert_eq!(root.size().height, 50.0);
+        assert!(errors.is_empty());
+    }
+}
@@ -0,0 +1,95 @@
+use crate::Layout;
+use std::sync::Arc;
+
+/// A reusable [`Layout`] configuration, for trees that are repeated
+/// many times such as the rows of a list.
+///
+/// The template keeps a single copy of the configuration, which is
+/// shared between clones of the template. Each call to
+/// [`instantiate`](Template::instantiate) creates a new tree, with new
+/// ids, that can be solved on its own.
+///
+/// # Example
+/// ```
+/// use cascada::{solve_layout, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, Size, Template, VerticalLayout};
+///
+/// let row = HorizontalLayout::new()
+///     .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 20.0)));
+/// let template = Template::new(row);
+///
+/// let rows = (0..3).map(|_| template.instantiate()).collect();
+/// let mut list = VerticalLayout::with_children(rows);
+/// solve_layout(&mut list, Size::unit(500.0));
+///
+/// assert_eq!(list.size().height, 60.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Template {
+    node: Arc<dyn Layout>,
+}
+
+impl Template {
+    /// Create a template from a layout. Any solved state, such as the
+    /// size and position, is reset.
+    pub fn new(layout: impl Layout + 'static) -> Self {
+        Self::from_boxed(Box::new(layout))
+    }
+
+    /// Create a template from a boxed layout.
+    pub fn from_boxed(mut layout: Box<dyn Layout>) -> Self {
+        layout.reset();
+        Self {
+            node: Arc::from(layout),
+        }
+    }
+
+    /// Get the layout the template was created from.
+    pub fn layout(&self) -> &dyn Layout {
+        self.node.as_ref()
+    }
+
+    /// Create a new tree from the template, every node in the tree is
+    /// given a new [`GlobalId`](crate::GlobalId).
+    pub fn instantiate(&self) -> Box<dyn Layout> {
+        self.node.clone_boxed()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        BlockLayout, EmptyLayout, HorizontalLayout, IntrinsicSize, Padding, Size, VerticalLayout,
+        solve_layout,
+    };
+    use std::collections::HashSet;
+
+    #[test]
+    fn instantiate_list_rows() {
+        let row = HorizontalLayout::new()
+            .spacing(10)
+            .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(20.0, 20.0)))
+            .add_child(
+                BlockLayout::new(
+                    EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(80.0, 20.0)),
+                )
+                .padding(Padding::all(5.0)),
+            );
+        let template = Template::new(row);
+
+        let rows = (0..100).map(|_| template.instantiate()).collect();
+        let mut list = VerticalLayout::with_children(rows);
+        let errors = solve_layout(&mut list, Size::unit(1000.0));
+
+        assert!(errors.is_empty());
+        assert_eq!(list.size(), Size::new(120.0, 3000.0));
+        for (i, row) in list.children().iter().enumerate() {
+            assert_eq!(row.position().y, i as f32 * 30.0);
+            assert_eq!(row.size(), Size::new(120.0, 30.0));
+        }
+
+        let ids: HashSet<_> = list.iter().map(|layout| layout.id()).collect();
+        assert_eq!(ids.len(), list.iter().count());
+        assert_eq!(template.layout().size(), Size::default());
+    }
+}
@@ -93,29 +93,96 @@ pub mod debug;
 mod error;
 mod layout;
 mod position;
+#[cfg(feature = "profiling")]
+pub mod profiling;
 mod size;
+#[cfg(feature = "taffy-interop")]
+mod taffy_interop;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 pub use constraints::*;
 pub use error::LayoutError;
 pub use layout::*;
 pub use position::Bounds;
+pub use position::HitRegion;
 pub use position::Position;
 pub use size::Size;
+use std::cell::Cell;
 use std::fmt::Debug;
 use std::sync::atomic::{AtomicU32, Ordering};
 
 static COUNTER: AtomicU32 = AtomicU32::new(0);
 
+thread_local! {
+    /// The next id of the active [`IdScope`] on this thread.
+    static SCOPE_COUNTER: Cell<Option<u32>> = const { Cell::new(None) };
+}
+
 /// A global unique identifier
 #[derive(Copy, Clone, PartialOrd, PartialEq, Eq, Debug, Ord, Hash)]
 pub struct GlobalId(u32);
 
 impl GlobalId {
     pub fn new() -> Self {
+        if let Some(id) = SCOPE_COUNTER.get() {
+            SCOPE_COUNTER.set(Some(id + 1));
+            return Self(id);
+        }
+
         Self(COUNTER.fetch_add(1, Ordering::Relaxed))
     }
 }
 
+/// While an [`IdScope`] is alive, new [`GlobalId`]s created on the same thread
+/// are numbered from zero. Trees built in separate scopes get the same ids,
+/// which keeps snapshots and other output that include ids reproducible.
+///
+/// Ids from a scope are not unique, they can be the same as ids created
+/// outside of the scope, so don't mix nodes from inside and outside a
+/// scope in the same tree.
+///
+/// # Example
+/// ```
+/// use cascada::{EmptyLayout, IdScope, Layout};
+///
+/// let first = {
+///     let _scope = IdScope::new();
+///     EmptyLayout::new()
+/// };
+///
+/// let second = {
+///     let _scope = IdScope::new();
+///     EmptyLayout::new()
+/// };
+///
+/// assert_eq!(first.id(), second.id());
+/// ```
+#[derive(Debug)]
+pub struct IdScope {
+    previous: Option<u32>,
+}
+
+impl IdScope {
+    /// Start a new scope, the scope ends when it is dropped.
+    pub fn new() -> Self {
+        let previous = SCOPE_COUNTER.replace(Some(0));
+        Self { previous }
+    }
+}
+
+impl Default for IdScope {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for IdScope {
+    fn drop(&mut self) {
+        SCOPE_COUNTER.set(self.previous);
+    }
+}
+
 impl Default for GlobalId {
     fn default() -> Self {
         Self::new()
@@ -138,6 +205,52 @@ pub enum AxisAlignment {
     Center,
     /// Place content at the end.
     End,
+    /// Stretch content to fill the cross axis, content with a fixed size
+    /// is placed at the start. On the main axis this is the same as
+    /// [`Start`](AxisAlignment::Start).
+    Stretch,
+}
+
+/// The alignment on both axes of a [`BlockLayout`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Alignment2D {
+    #[default]
+    TopLeft,
+    TopCenter,
+    TopRight,
+    CenterLeft,
+    Center,
+    CenterRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+impl Alignment2D {
+    /// Get the horizontal and vertical [`AxisAlignment`].
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{Alignment2D, AxisAlignment};
+    ///
+    /// let (horizontal, vertical) = Alignment2D::BottomLeft.axes();
+    /// assert_eq!(horizontal, AxisAlignment::Start);
+    /// assert_eq!(vertical, AxisAlignment::End);
+    /// ```
+    pub const fn axes(&self) -> (AxisAlignment, AxisAlignment) {
+        use AxisAlignment::{Center, End, Start};
+        match self {
+            Self::TopLeft => (Start, Start),
+            Self::TopCenter => (Center, Start),
+            Self::TopRight => (End, Start),
+            Self::CenterLeft => (Start, Center),
+            Self::Center => (Center, Center),
+            Self::CenterRight => (End, Center),
+            Self::BottomLeft => (Start, End),
+            Self::BottomCenter => (Center, End),
+            Self::BottomRight => (End, End),
+        }
+    }
 }
 
 /// The space between the edges of a [`Layout`] node and its content.
@@ -205,6 +318,94 @@ impl Padding {
         Self::new(padding, padding, padding, padding)
     }
 
+    /// Create padding with only the left and right sides.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::Padding;
+    ///
+    /// let padding = Padding::horizontal(20.0);
+    ///
+    /// assert_eq!(padding.left,20.0);
+    /// assert_eq!(padding.right,20.0);
+    /// assert_eq!(padding.vertical_sum(),0.0);
+    /// ```
+    pub const fn horizontal(padding: f32) -> Self {
+        Self::new(padding, padding, 0.0, 0.0)
+    }
+
+    /// Create padding with only the top and bottom sides.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::Padding;
+    ///
+    /// let padding = Padding::vertical(20.0);
+    ///
+    /// assert_eq!(padding.top,20.0);
+    /// assert_eq!(padding.bottom,20.0);
+    /// assert_eq!(padding.horizontal_sum(),0.0);
+    /// ```
+    pub const fn vertical(padding: f32) -> Self {
+        Self::new(0.0, 0.0, padding, padding)
+    }
+
+    /// Returns the padding with the left side set to `value`.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::Padding;
+    ///
+    /// let padding = Padding::all(10.0).with_left(24.0);
+    ///
+    /// assert_eq!(padding.left,24.0);
+    /// ```
+    pub const fn with_left(self, value: f32) -> Self {
+        Self::new(value, self.right, self.top, self.bottom)
+    }
+
+    /// Returns the padding with the right side set to `value`.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::Padding;
+    ///
+    /// let padding = Padding::all(10.0).with_right(24.0);
+    ///
+    /// assert_eq!(padding.right,24.0);
+    /// ```
+    pub const fn with_right(self, value: f32) -> Self {
+        Self::new(self.left, value, self.top, self.bottom)
+    }
+
+    /// Returns the padding with the top side set to `value`.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::Padding;
+    ///
+    /// let padding = Padding::all(10.0).with_top(24.0);
+    ///
+    /// assert_eq!(padding.top,24.0);
+    /// ```
+    pub const fn with_top(self, value: f32) -> Self {
+        Self::new(self.left, self.right, value, self.bottom)
+    }
+
+    /// Returns the padding with the bottom side set to `value`.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::Padding;
+    ///
+    /// let padding = Padding::all(10.0).with_bottom(24.0);
+    ///
+    /// assert_eq!(padding.bottom,24.0);
+    /// ```
+    pub const fn with_bottom(self, value: f32) -> Self {
+        Self::new(self.left, self.right, self.top, value)
+    }
+
     /// The sum of the top and bottom padding.
     ///
     /// # Example
@@ -248,15 +449,312 @@ impl Padding {
     pub const fn sum(&self) -> f32 {
         self.horizontal_sum() + self.vertical_sum()
     }
+
+    /// Adds the [`Border`] thickness to each side of the padding.
+    pub(crate) const fn with_border(self, border: Border) -> Self {
+        Self::new(
+            self.left + border.left,
+            self.right + border.right,
+            self.top + border.top,
+            self.bottom + border.bottom,
+        )
+    }
+}
+
+/// A padding length, either in pixels or as a percentage of the space a
+/// node is given by its parent.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+pub enum PaddingValue {
+    /// A length in pixels.
+    Px(f32),
+    /// A percentage, where `10.0` is 10%, of the width for the left and
+    /// right sides and of the height for the top and bottom sides.
+    Percent(f32),
+}
+
+impl PaddingValue {
+    /// Resolve the padding in pixels, given the `length` that percentages
+    /// are relative to.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::PaddingValue;
+    ///
+    /// assert_eq!(PaddingValue::Px(20.0).resolve(800.0), 20.0);
+    /// assert_eq!(PaddingValue::Percent(10.0).resolve(800.0), 80.0);
+    /// ```
+    pub fn resolve(self, length: f32) -> f32 {
+        match self {
+            Self::Px(value) => value,
+            Self::Percent(percent) => (percent / 100.0 * length).max(0.0),
+        }
+    }
+}
+
+impl Default for PaddingValue {
+    fn default() -> Self {
+        Self::Px(0.0)
+    }
+}
+
+impl From<f32> for PaddingValue {
+    fn from(value: f32) -> Self {
+        Self::Px(value)
+    }
+}
+
+/// [`Padding`] where each side is a [`PaddingValue`], so that sides can be
+/// a percentage of the space a node is given by its parent.
+///
+/// Percentages are resolved when the max constraints are solved, so they
+/// are not part of a node's min size. They are meant for nodes that flex
+/// or have a fixed size.
+///
+/// # Example
+/// ```
+/// use cascada::{solve_layout, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, PaddingValue, RelativePadding, Size};
+///
+/// let mut layout = HorizontalLayout::new()
+///     .intrinsic_size(IntrinsicSize::fill())
+///     .padding(RelativePadding::horizontal(PaddingValue::Percent(5.0)))
+///     .add_child(EmptyLayout::new());
+/// solve_layout(&mut layout, Size::unit(400.0));
+///
+/// assert_eq!(layout.children()[0].position().x, 20.0);
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, PartialOrd, Debug)]
+pub struct RelativePadding {
+    pub left: PaddingValue,
+    pub right: PaddingValue,
+    pub top: PaddingValue,
+    pub bottom: PaddingValue,
+}
+
+impl RelativePadding {
+    pub const fn new(
+        left: PaddingValue,
+        right: PaddingValue,
+        top: PaddingValue,
+        bottom: PaddingValue,
+    ) -> Self {
+        Self {
+            left,
+            right,
+            top,
+            bottom,
+        }
+    }
+
+    /// Create a [`RelativePadding`] with equal sides.
+    pub const fn all(value: PaddingValue) -> Self {
+        Self::new(value, value, value, value)
+    }
+
+    /// Create a [`RelativePadding`] with equal left and right sides.
+    pub const fn horizontal(value: PaddingValue) -> Self {
+        Self::new(value, value, PaddingValue::Px(0.0), PaddingValue::Px(0.0))
+    }
+
+    /// Create a [`RelativePadding`] with equal top and bottom sides.
+    pub const fn vertical(value: PaddingValue) -> Self {
+        Self::new(PaddingValue::Px(0.0), PaddingValue::Px(0.0), value, value)
+    }
+
+    /// Returns `true` if any of the sides is a percentage.
+    pub fn is_relative(&self) -> bool {
+        [self.left, self.right, self.top, self.bottom]
+            .iter()
+            .any(|value| matches!(value, PaddingValue::Percent(_)))
+    }
+
+    /// Resolve the padding in pixels, given the `space` that percentages
+    /// are relative to.
+    pub fn resolve(&self, space: Size) -> Padding {
+        Padding::new(
+            self.left.resolve(space.width),
+            self.right.resolve(space.width),
+            self.top.resolve(space.height),
+            self.bottom.resolve(space.height),
+        )
+    }
+}
+
+impl From<Padding> for RelativePadding {
+    fn from(padding: Padding) -> Self {
+        Self::new(
+            PaddingValue::Px(padding.left),
+            PaddingValue::Px(padding.right),
+            PaddingValue::Px(padding.top),
+            PaddingValue::Px(padding.bottom),
+        )
+    }
+}
+
+impl std::ops::Add for Padding {
+    type Output = Padding;
+
+    /// Adds each side of two [`Padding`]s.
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.left + rhs.left,
+            self.right + rhs.right,
+            self.top + rhs.top,
+            self.bottom + rhs.bottom,
+        )
+    }
+}
+
+impl std::ops::Sub for Padding {
+    type Output = Padding;
+
+    /// Subtracts each side of two [`Padding`]s, clamping the sides at zero.
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(
+            (self.left - rhs.left).max(0.0),
+            (self.right - rhs.right).max(0.0),
+            (self.top - rhs.top).max(0.0),
+            (self.bottom - rhs.bottom).max(0.0),
+        )
+    }
+}
+
+impl std::ops::Mul<f32> for Padding {
+    type Output = Padding;
+
+    /// Scales each side of the [`Padding`].
+    ///
+    /// # Panics
+    /// Panics if the factor is negative.
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self::new(
+            self.left * rhs,
+            self.right * rhs,
+            self.top * rhs,
+            self.bottom * rhs,
+        )
+    }
+}
+
+/// The thickness of the border around a [`Layout`] node.
+///
+/// Borders take up space the same way [`Padding`] does, the content of a
+/// node is inset by both its border and its padding.
+#[derive(Clone, Copy, Default, PartialEq, PartialOrd, Debug)]
+pub struct Border {
+    /// The left border thickness.
+    pub left: f32,
+    /// The right border thickness.
+    pub right: f32,
+    /// The top border thickness.
+    pub top: f32,
+    /// The bottom border thickness.
+    pub bottom: f32,
+}
+
+impl Border {
+    /// Creates a new [`Border`].
+    ///
+    /// # Panics
+    /// Panics if sides are negative.
+    pub const fn new(left: f32, right: f32, top: f32, bottom: f32) -> Self {
+        assert!(
+            left >= 0.0 && right >= 0.0 && top >= 0.0 && bottom >= 0.0,
+            "Border sides must be positive."
+        );
+        Self {
+            left,
+            right,
+            top,
+            bottom,
+        }
+    }
+
+    /// Create a [`Border`] with equal sides.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::Border;
+    ///
+    /// let border = Border::all(2.0);
+    ///
+    /// assert_eq!(border.left,2.0);
+    /// assert_eq!(border.left,border.right);
+    /// assert_eq!(border.bottom,border.top);
+    /// ```
+    pub const fn all(thickness: f32) -> Self {
+        Self::new(thickness, thickness, thickness, thickness)
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
+    #[test]
+    fn scoped_ids_are_sequential() {
+        let build = || {
+            let _scope = IdScope::new();
+            HorizontalLayout::new()
+                .add_child(EmptyLayout::new())
+                .add_child(BlockLayout::new(EmptyLayout::new()))
+        };
+
+        let first = build();
+        let second = build();
+
+        let first: Vec<GlobalId> = first.iter().map(|node| node.id()).collect();
+        let second: Vec<GlobalId> = second.iter().map(|node| node.id()).collect();
+        assert_eq!(first, second);
+
+        let mut ids = first.clone();
+        ids.sort();
+        assert_eq!(ids, (0..4).map(GlobalId).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn nested_id_scopes() {
+        let _outer = IdScope::new();
+        assert_eq!(GlobalId::new(), GlobalId(0));
+        {
+            let _inner = IdScope::new();
+            assert_eq!(GlobalId::new(), GlobalId(0));
+        }
+        assert_eq!(GlobalId::new(), GlobalId(1));
+    }
+
     #[test]
     #[should_panic]
     fn padding_no_negative() {
         Padding::new(0.0, 0.0, 0.0, -35.0);
     }
+
+    #[test]
+    fn add_padding() {
+        let padding = Padding::all(10.0) + Padding::symmetric(5.0, 0.0);
+        assert_eq!(padding, Padding::new(10.0, 10.0, 15.0, 15.0));
+    }
+
+    #[test]
+    fn sub_padding_saturates() {
+        let padding = Padding::all(10.0) - Padding::symmetric(5.0, 20.0);
+        assert_eq!(padding, Padding::new(0.0, 0.0, 5.0, 5.0));
+    }
+
+    #[test]
+    fn scale_padding() {
+        let padding = Padding::new(10.0, 5.0, 2.0, 0.0) * 2.0;
+        assert_eq!(padding, Padding::new(20.0, 10.0, 4.0, 0.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn scale_padding_no_negative() {
+        let _ = Padding::all(10.0) * -1.0;
+    }
+
+    #[test]
+    #[should_panic]
+    fn border_no_negative() {
+        Border::new(-1.0, 0.0, 0.0, 0.0);
+    }
 }
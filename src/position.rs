@@ -1,4 +1,4 @@
-use crate::Size;
+use crate::{Padding, Size};
 use std::fmt::Display;
 use std::ops::{Add, AddAssign, Sub, SubAssign};
 
@@ -59,6 +59,18 @@ impl Position {
     }
 }
 
+/// The part of a layout node that a point lands in, returned by
+/// [`Layout::hit_region`](crate::Layout::hit_region).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HitRegion {
+    /// The point is inside the content of the node.
+    Content,
+    /// The point is inside the node, but in its padding or border.
+    Padding,
+    /// The point is outside of the node.
+    Outside,
+}
+
 /// The bounds of any object that has a [`Size`] and [`Position`].
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
 pub struct Bounds {
@@ -99,6 +111,123 @@ impl Bounds {
 
         false
     }
+
+    /// Check if another [`Bounds`] lies entirely within these [`Bounds`].
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{Position,Bounds,Size};
+    ///
+    /// let outer = Bounds::new(Position::unit(0.0),Size::unit(100.0));
+    /// let inner = Bounds::new(Position::unit(10.0),Size::unit(50.0));
+    ///
+    /// assert!(outer.contains(&inner));
+    /// assert!(!inner.contains(&outer));
+    /// ```
+    pub fn contains(&self, other: &Bounds) -> bool {
+        other.x[0] >= self.x[0]
+            && other.x[1] <= self.x[1]
+            && other.y[0] >= self.y[0]
+            && other.y[1] <= self.y[1]
+    }
+
+    /// Check if these [`Bounds`] overlap with another [`Bounds`]. Bounds
+    /// that only share an edge don't overlap.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{Position,Bounds,Size};
+    ///
+    /// let a = Bounds::new(Position::unit(0.0),Size::unit(100.0));
+    /// let b = Bounds::new(Position::unit(50.0),Size::unit(100.0));
+    /// let c = Bounds::new(Position::new(100.0,0.0),Size::unit(100.0));
+    ///
+    /// assert!(a.intersects(&b));
+    /// assert!(!a.intersects(&c));
+    /// ```
+    pub fn intersects(&self, other: &Bounds) -> bool {
+        self.x[0] < other.x[1]
+            && other.x[0] < self.x[1]
+            && self.y[0] < other.y[1]
+            && other.y[0] < self.y[1]
+    }
+
+    /// Shrink the [`Bounds`] by `amount` on every side. If the bounds are
+    /// too small they collapse to a zero sized box at their center.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{Position,Bounds,Size};
+    ///
+    /// let bounds = Bounds::new(Position::unit(0.0),Size::unit(100.0));
+    ///
+    /// assert_eq!(bounds.inset(10.0),Bounds::new(Position::unit(10.0),Size::unit(80.0)));
+    /// assert_eq!(bounds.inset(80.0),Bounds::new(Position::unit(50.0),Size::unit(0.0)));
+    /// ```
+    pub fn inset(self, amount: f32) -> Bounds {
+        self.inset_sides(amount, amount, amount, amount)
+    }
+
+    /// Grow the [`Bounds`] by `amount` on every side.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{Position,Bounds,Size};
+    ///
+    /// let bounds = Bounds::new(Position::unit(10.0),Size::unit(100.0));
+    ///
+    /// assert_eq!(bounds.outset(5.0),Bounds::new(Position::unit(5.0),Size::unit(110.0)));
+    /// ```
+    pub fn outset(self, amount: f32) -> Bounds {
+        self.inset(-amount)
+    }
+
+    /// Shrink each side of the [`Bounds`] by the matching side of the
+    /// `padding`. An axis that is inset past zero collapses to the middle
+    /// of its edges.
+    ///
+    /// # Example
+    /// ```
+    /// use cascada::{Position,Bounds,Padding,Size};
+    ///
+    /// let bounds = Bounds::new(Position::unit(0.0),Size::unit(100.0));
+    /// let inset = bounds.inset_by(Padding::new(10.0,20.0,0.0,50.0));
+    ///
+    /// assert_eq!(inset,Bounds::new(Position::new(10.0,0.0),Size::new(70.0,50.0)));
+    /// ```
+    pub fn inset_by(self, padding: Padding) -> Bounds {
+        self.inset_sides(padding.left, padding.right, padding.top, padding.bottom)
+    }
+
+    fn inset_sides(self, left: f32, right: f32, top: f32, bottom: f32) -> Bounds {
+        let collapse = |start: f32, end: f32| {
+            if start > end {
+                let center = (start + end) / 2.0;
+                [center, center]
+            } else {
+                [start, end]
+            }
+        };
+
+        Self {
+            x: collapse(self.x[0] + left, self.x[1] - right),
+            y: collapse(self.y[0] + top, self.y[1] - bottom),
+        }
+    }
+}
+
+impl From<(Position, Size)> for Bounds {
+    fn from((position, size): (Position, Size)) -> Self {
+        Self::new(position, size)
+    }
+}
+
+impl From<Bounds> for (Position, Size) {
+    fn from(bounds: Bounds) -> Self {
+        let position = Position::new(bounds.x[0], bounds.y[0]);
+        let size = Size::new(bounds.x[1] - bounds.x[0], bounds.y[1] - bounds.y[0]);
+        (position, size)
+    }
 }
 
 impl Add for Position {
@@ -198,4 +327,56 @@ mod test {
         let string = format!("{pos:.2}");
         assert_eq!(string, "50.00x20.24");
     }
+
+    #[test]
+    fn inset_bounds() {
+        let bounds = Bounds::new(Position::new(10.0, 20.0), Size::new(100.0, 50.0));
+        let inset = Bounds::new(Position::new(15.0, 25.0), Size::new(90.0, 40.0));
+        assert_eq!(bounds.inset(5.0), inset);
+    }
+
+    #[test]
+    fn outset_bounds() {
+        let bounds = Bounds::new(Position::new(10.0, 20.0), Size::new(100.0, 50.0));
+        let outset = Bounds::new(Position::new(8.0, 18.0), Size::new(104.0, 54.0));
+        assert_eq!(bounds.outset(2.0), outset);
+    }
+
+    #[test]
+    fn over_inset_collapses_to_center() {
+        let bounds = Bounds::new(Position::new(10.0, 20.0), Size::new(100.0, 50.0));
+        let inset = bounds.inset(40.0);
+        assert_eq!(
+            inset,
+            Bounds::new(Position::new(50.0, 45.0), Size::new(20.0, 0.0))
+        );
+        let collapsed = bounds.inset(500.0);
+        assert_eq!(
+            collapsed,
+            Bounds::new(Position::new(60.0, 45.0), Size::default())
+        );
+    }
+
+    #[test]
+    fn bounds_from_position_and_size() {
+        let position = Position::new(10.0, 20.0);
+        let size = Size::new(100.0, 50.0);
+
+        let bounds: Bounds = (position, size).into();
+        assert_eq!(bounds, Bounds::new(position, size));
+        assert_eq!(<(Position, Size)>::from(bounds), (position, size));
+    }
+
+    #[test]
+    fn position_and_size_from_bounds() {
+        let bounds = Bounds {
+            x: [-5.0, 15.0],
+            y: [30.0, 42.5],
+        };
+
+        let (position, size) = bounds.into();
+        assert_eq!(position, Position::new(-5.0, 30.0));
+        assert_eq!(size, Size::new(20.0, 12.5));
+        assert_eq!(Bounds::from((position, size)), bounds);
+    }
 }
@@ -0,0 +1,189 @@
+//! Timing of the layout passes, enabled with the `profiling` feature.
+//!
+//! ```
+//! use cascada::profiling::solve_layout_profiled;
+//! use cascada::{EmptyLayout, HorizontalLayout, IntrinsicSize, Size};
+//!
+//! let mut layout = HorizontalLayout::new()
+//!     .intrinsic_size(IntrinsicSize::fill())
+//!     .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()));
+//!
+//! let (errors, stats) = solve_layout_profiled(&mut layout, Size::unit(500.0));
+//! assert!(errors.is_empty());
+//! assert!(stats.get("HorizontalLayout").is_some());
+//! ```
+use crate::{Layout, LayoutError, Size, solve_layout};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+/// A pass of the layout solver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pass {
+    MinConstraints,
+    MaxConstraints,
+    Position,
+}
+
+/// The time spent in each pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PassDurations {
+    pub min_constraints: Duration,
+    pub max_constraints: Duration,
+    pub position: Duration,
+}
+
+impl PassDurations {
+    /// The time spent in all the passes.
+    pub fn total(&self) -> Duration {
+        self.min_constraints + self.max_constraints + self.position
+    }
+
+    fn add(&mut self, pass: Pass, duration: Duration) {
+        match pass {
+            Pass::MinConstraints => self.min_constraints += duration,
+            Pass::MaxConstraints => self.max_constraints += duration,
+            Pass::Position => self.position += duration,
+        }
+    }
+}
+
+/// The time spent solving the layout, grouped by the type of node.
+///
+/// The time for each node excludes the time spent in its children.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SolveStats {
+    nodes: HashMap<&'static str, PassDurations>,
+}
+
+impl SolveStats {
+    /// Get the durations of a node type, e.g. `"HorizontalLayout"`.
+    pub fn get(&self, node_type: &str) -> Option<PassDurations> {
+        self.nodes.get(node_type).copied()
+    }
+
+    /// Iterate over the durations of each node type.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, PassDurations)> + '_ {
+        self.nodes
+            .iter()
+            .map(|(node, durations)| (*node, *durations))
+    }
+
+    /// The durations of every node type combined.
+    pub fn total(&self) -> PassDurations {
+        let mut total = PassDurations::default();
+        for durations in self.nodes.values() {
+            total.min_constraints += durations.min_constraints;
+            total.max_constraints += durations.max_constraints;
+            total.position += durations.position;
+        }
+        total
+    }
+}
+
+#[derive(Default)]
+struct Recorder {
+    stats: SolveStats,
+    /// The time spent in the children of each timer that is running.
+    children: Vec<Duration>,
+}
+
+/// Times a node's pass until it is dropped.
+pub(crate) struct Timer {
+    node: &'static str,
+    pass: Pass,
+    start: Instant,
+}
+
+/// Start timing a pass of a node, this does nothing unless the layout
+/// is being solved with [`solve_layout_profiled`].
+pub(crate) fn timer(node: &'static str, pass: Pass) -> Timer {
+    RECORDER.with_borrow_mut(|recorder| {
+        if let Some(recorder) = recorder {
+            recorder.children.push(Duration::ZERO);
+        }
+    });
+
+    Timer {
+        node,
+        pass,
+        start: Instant::now(),
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        RECORDER.with_borrow_mut(|recorder| {
+            let Some(recorder) = recorder else { return };
+            let children = recorder.children.pop().unwrap_or_default();
+            recorder
+                .stats
+                .nodes
+                .entry(self.node)
+                .or_default()
+                .add(self.pass, elapsed.saturating_sub(children));
+
+            if let Some(parent) = recorder.children.last_mut() {
+                *parent += elapsed;
+            }
+        });
+    }
+}
+
+/// Solve the layout, the same as [`solve_layout`], and record how long
+/// each type of node spent in each pass.
+pub fn solve_layout_profiled(
+    root: &mut dyn Layout,
+    window_size: Size,
+) -> (Vec<LayoutError>, SolveStats) {
+    RECORDER.set(Some(Recorder::default()));
+    let errors = solve_layout(root, window_size);
+    let stats = RECORDER
+        .take()
+        .map(|recorder| recorder.stats)
+        .unwrap_or_default();
+    (errors, stats)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{BlockLayout, EmptyLayout, HorizontalLayout, IntrinsicSize, VerticalLayout};
+
+    #[test]
+    fn record_node_types() {
+        let mut column = VerticalLayout::new().intrinsic_size(IntrinsicSize::fill());
+        for _ in 0..50 {
+            let row = HorizontalLayout::new()
+                .intrinsic_size(IntrinsicSize::fill())
+                .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()))
+                .add_child(BlockLayout::new(EmptyLayout::new()));
+            column = column.add_child(row);
+        }
+
+        let (_, stats) = solve_layout_profiled(&mut column, Size::unit(1000.0));
+
+        assert_eq!(stats.iter().count(), 4);
+        for node in [
+            "EmptyLayout",
+            "BlockLayout",
+            "HorizontalLayout",
+            "VerticalLayout",
+        ] {
+            assert!(stats.get(node).is_some());
+        }
+        assert!(stats.total().total() > Duration::ZERO);
+    }
+
+    #[test]
+    fn nothing_recorded_outside_profiling() {
+        let mut layout = EmptyLayout::new();
+        solve_layout(&mut layout, Size::unit(100.0));
+        RECORDER.with_borrow(|recorder| assert!(recorder.is_none()));
+    }
+}
@@ -38,6 +38,45 @@ impl Size {
     pub const fn unit(value: f32) -> Size {
         Self::new(value, value)
     }
+
+    /// Subtract two [`Size`]s, returning `None` if either the width
+    /// or height would be negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cascada::Size;
+    ///
+    /// let size = Size::unit(100.0).checked_sub(Size::unit(50.0));
+    /// assert_eq!(size,Some(Size::unit(50.0)));
+    ///
+    /// let size = Size::unit(50.0).checked_sub(Size::new(20.0,80.0));
+    /// assert_eq!(size,None);
+    /// ```
+    pub const fn checked_sub(self, rhs: Size) -> Option<Size> {
+        let size = Self::new(self.width - rhs.width, self.height - rhs.height);
+        if size.width < 0.0 || size.height < 0.0 {
+            return None;
+        }
+        Some(size)
+    }
+
+    /// Subtract two [`Size`]s, clamping the width and height at zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cascada::Size;
+    ///
+    /// let size = Size::unit(50.0).saturating_sub(Size::new(20.0,80.0));
+    /// assert_eq!(size,Size::new(30.0,0.0));
+    /// ```
+    pub const fn saturating_sub(self, rhs: Size) -> Size {
+        Self::new(
+            (self.width - rhs.width).max(0.0),
+            (self.height - rhs.height).max(0.0),
+        )
+    }
 }
 
 impl Add for Size {
@@ -236,6 +275,18 @@ mod test {
         assert_eq!(string, "50x20.24242");
     }
 
+    #[test]
+    fn checked_sub_negative() {
+        let size = Size::unit(50.0).checked_sub(Size::unit(80.0));
+        assert_eq!(size, None);
+    }
+
+    #[test]
+    fn saturating_sub_negative() {
+        let size = Size::unit(50.0).saturating_sub(Size::unit(80.0));
+        assert_eq!(size, Size::unit(0.0));
+    }
+
     #[test]
     fn display_with_precision() {
         let size = Size::new(50.0, 20.24242);
@@ -0,0 +1,153 @@
+//! Conversions to [`taffy`] styles, for mirroring a tree into taffy.
+use crate::{AxisAlignment, Border, BoxSizing, IntrinsicSize, Padding};
+use taffy::{AlignItems, Dimension, FlexDirection, JustifyContent, LengthPercentage, Rect, Style};
+
+/// The style shared by every node, built from the size, padding, border
+/// and cross axis alignment.
+pub(crate) fn base_style(
+    intrinsic_size: IntrinsicSize,
+    padding: Padding,
+    border: Border,
+    cross_self: Option<AxisAlignment>,
+) -> Style {
+    let dimension = |sizing: BoxSizing| match sizing {
+        BoxSizing::Fixed(size) => Dimension::length(size),
+        BoxSizing::Flex(_) | BoxSizing::FlexF(_) | BoxSizing::Shrink => Dimension::auto(),
+    };
+
+    Style {
+        size: taffy::Size {
+            width: dimension(intrinsic_size.width),
+            height: dimension(intrinsic_size.height),
+        },
+        flex_shrink: 0.0,
+        padding: Rect {
+            left: LengthPercentage::length(padding.left),
+            right: LengthPercentage::length(padding.right),
+            top: LengthPercentage::length(padding.top),
+            bottom: LengthPercentage::length(padding.bottom),
+        },
+        border: Rect {
+            left: LengthPercentage::length(border.left),
+            right: LengthPercentage::length(border.right),
+            top: LengthPercentage::length(border.top),
+            bottom: LengthPercentage::length(border.bottom),
+        },
+        align_self: cross_self.map(align_items),
+        ..Default::default()
+    }
+}
+
+/// The style of a node as a child of a flex container with the given
+/// `direction`. The flex factor on the main axis grows the node and
+/// flexing on the cross axis stretches it.
+pub(crate) fn child_style(
+    style: Style,
+    intrinsic_size: IntrinsicSize,
+    direction: FlexDirection,
+) -> Style {
+    let (main, cross) = match direction {
+        FlexDirection::Row | FlexDirection::RowReverse => {
+            (intrinsic_size.width, intrinsic_size.height)
+        }
+        FlexDirection::Column | FlexDirection::ColumnReverse => {
+            (intrinsic_size.height, intrinsic_size.width)
+        }
+    };
+    let stretch = matches!(cross, BoxSizing::Flex(_) | BoxSizing::FlexF(_));
+
+    Style {
+        flex_grow: main.flex_factor(),
+        align_self: if stretch {
+            Some(AlignItems::Stretch)
+        } else {
+            style.align_self
+        },
+        ..style
+    }
+}
+
+/// The style of a flex container, which is a row or column of children.
+pub(crate) fn flex_style(
+    base: Style,
+    direction: FlexDirection,
+    spacing: u32,
+    main_axis_alignment: AxisAlignment,
+    cross_axis_alignment: AxisAlignment,
+) -> Style {
+    let gap = LengthPercentage::length(spacing as f32);
+    Style {
+        flex_direction: direction,
+        gap: match direction {
+            FlexDirection::Row | FlexDirection::RowReverse => taffy::Size {
+                width: gap,
+                height: LengthPercentage::length(0.0),
+            },
+            FlexDirection::Column | FlexDirection::ColumnReverse => taffy::Size {
+                width: LengthPercentage::length(0.0),
+                height: gap,
+            },
+        },
+        justify_content: Some(justify_content(main_axis_alignment)),
+        align_items: Some(align_items(cross_axis_alignment)),
+        ..base
+    }
+}
+
+fn justify_content(alignment: AxisAlignment) -> JustifyContent {
+    match alignment {
+        AxisAlignment::Start => JustifyContent::Start,
+        AxisAlignment::Center => JustifyContent::Center,
+        AxisAlignment::End => JustifyContent::End,
+        AxisAlignment::Stretch => JustifyContent::Stretch,
+    }
+}
+
+fn align_items(alignment: AxisAlignment) -> AlignItems {
+    match alignment {
+        AxisAlignment::Start => AlignItems::Start,
+        AxisAlignment::Center => AlignItems::Center,
+        AxisAlignment::End => AlignItems::End,
+        AxisAlignment::Stretch => AlignItems::Stretch,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        BoxSizing, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, Padding, VerticalLayout,
+    };
+    use taffy::{AlignItems, Dimension, FlexDirection, LengthPercentage};
+
+    #[test]
+    fn flex_row_style() {
+        let layout = HorizontalLayout::new()
+            .spacing(12)
+            .padding(Padding::all(8.0))
+            .intrinsic_size(IntrinsicSize::fixed(400.0, 100.0))
+            .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()));
+
+        let style = layout.to_taffy_style();
+        assert_eq!(style.flex_direction, FlexDirection::Row);
+        assert_eq!(style.gap.width, LengthPercentage::length(12.0));
+        assert_eq!(style.size.width, Dimension::length(400.0));
+        assert_eq!(style.padding.left, LengthPercentage::length(8.0));
+
+        let child = layout.children()[0].to_taffy_child_style(style.flex_direction);
+        assert_eq!(child.flex_grow, 1.0);
+    }
+
+    #[test]
+    fn child_grows_on_parent_main_axis() {
+        let child = EmptyLayout::new().intrinsic_size(IntrinsicSize {
+            width: BoxSizing::Flex(1),
+            height: BoxSizing::Shrink,
+        });
+        let layout = VerticalLayout::new().add_child(child);
+
+        let style = layout.to_taffy_style();
+        let child = layout.children()[0].to_taffy_child_style(style.flex_direction);
+        assert_eq!(child.flex_grow, 0.0);
+        assert_eq!(child.align_self, Some(AlignItems::Stretch));
+    }
+}
@@ -0,0 +1,133 @@
+//! Snapshot testing for solved layouts, enabled with the `testing` feature.
+//!
+//! The geometry of a solved layout is written to a snapshot file the first
+//! time a test runs, later runs compare the layout against that file. To
+//! update the snapshots after an intended change, run the tests with the
+//! `CASCADA_UPDATE_SNAPSHOTS` environment variable set.
+//!
+//! Snapshots don't include the [`GlobalId`](crate::GlobalId) of each node,
+//! use [`snapshot_with_ids`] together with an [`IdScope`](crate::IdScope)
+//! if the ids should be part of the snapshot.
+//!
+//! ```no_run
+//! use cascada::testing::assert_layout_snapshot;
+//! use cascada::{solve_layout, EmptyLayout, HorizontalLayout, IntrinsicSize, Size};
+//!
+//! let mut layout = HorizontalLayout::new()
+//!     .intrinsic_size(IntrinsicSize::fill())
+//!     .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()));
+//!
+//! solve_layout(&mut layout, Size::unit(500.0));
+//! assert_layout_snapshot(&layout, "row");
+//! ```
+use crate::Layout;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// The environment variable used to update the snapshots.
+pub const UPDATE_SNAPSHOTS: &str = "CASCADA_UPDATE_SNAPSHOTS";
+
+/// Serialize the size and position of every node in the tree, with each
+/// child indented under its parent. The output only depends on the layout,
+/// so it is the same between runs.
+///
+/// # Example
+/// ```
+/// use cascada::testing::snapshot;
+/// use cascada::{solve_layout, BlockLayout, EmptyLayout, IntrinsicSize, Size};
+///
+/// let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(20.0, 20.0));
+/// let mut layout = BlockLayout::new(child);
+/// solve_layout(&mut layout, Size::unit(500.0));
+///
+/// assert_eq!(
+///     snapshot(&layout),
+///     "BlockLayout size=20x20 pos=0x0\n  EmptyLayout size=20x20 pos=0x0\n"
+/// );
+/// ```
+pub fn snapshot(root: &dyn Layout) -> String {
+    let mut output = String::new();
+    write_node(&mut output, root, 0);
+    output
+}
+
+/// Serialize the tree in the same way as [`snapshot`], including the
+/// id of every node. The ids are only the same between runs if the tree
+/// is built inside an [`IdScope`](crate::IdScope).
+///
+/// # Example
+/// ```
+/// use cascada::testing::snapshot_with_ids;
+/// use cascada::{EmptyLayout, IdScope};
+///
+/// let _scope = IdScope::new();
+/// let layout = EmptyLayout::new();
+///
+/// assert_eq!(snapshot_with_ids(&layout), "EmptyLayout id=0 size=0x0 pos=0x0\n");
+/// ```
+pub fn snapshot_with_ids(root: &dyn Layout) -> String {
+    let mut output = String::new();
+    write_node_with_ids(&mut output, root, 0);
+    output
+}
+
+fn write_node(output: &mut String, node: &dyn Layout, depth: usize) {
+    let whitespace = "  ".repeat(depth);
+    let _ = writeln!(
+        output,
+        "{whitespace}{} size={} pos={}",
+        node.label(),
+        node.size(),
+        node.position()
+    );
+    for child in node.children() {
+        write_node(output, child.as_ref(), depth + 1);
+    }
+}
+
+fn write_node_with_ids(output: &mut String, node: &dyn Layout, depth: usize) {
+    let whitespace = "  ".repeat(depth);
+    let _ = writeln!(output, "{whitespace}{}", node.summary());
+    for child in node.children() {
+        write_node_with_ids(output, child.as_ref(), depth + 1);
+    }
+}
+
+/// Assert that the solved layout matches the snapshot called `name`.
+///
+/// Snapshots are stored in `tests/snapshots/<name>.snap` in the directory
+/// of the crate being tested. If the snapshot doesn't exist, or the
+/// [`UPDATE_SNAPSHOTS`] environment variable is set, the snapshot is
+/// written instead.
+///
+/// # Panics
+///
+/// Panics if the layout doesn't match the snapshot, or if the snapshot
+/// can't be read or written.
+pub fn assert_layout_snapshot(root: &dyn Layout, name: &str) {
+    let actual = snapshot(root);
+    let path = snapshot_path(name);
+
+    if std::env::var_os(UPDATE_SNAPSHOTS).is_some() || !path.exists() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("Failed to create the snapshot directory");
+        }
+        std::fs::write(&path, actual).expect("Failed to write the snapshot");
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).expect("Failed to read the snapshot");
+    assert!(
+        expected == actual,
+        "Layout doesn't match the snapshot {}\n\nExpected:\n{expected}\nActual:\n{actual}\nSet {UPDATE_SNAPSHOTS} to update the snapshot.",
+        path.display()
+    );
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    let root = std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default();
+    PathBuf::from(root)
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{name}.snap"))
+}
@@ -1,6 +1,6 @@
 use cascada::{
-    AxisAlignment, BlockLayout, EmptyLayout, IntrinsicSize, Layout, Padding, Position, Size,
-    solve_layout,
+    Alignment2D, AxisAlignment, BlockLayout, EmptyLayout, IntrinsicSize, Layout, Padding, Position,
+    Size, solve_layout,
 };
 
 #[test]
@@ -55,9 +55,10 @@ fn test_end_alignment() {
 
     solve_layout(&mut root, window);
 
+    let child_size = root.child().size();
     let mut child_1_pos = Position {
-        x: root.position().x + root.size().width,
-        y: root.position().y + root.size().height,
+        x: root.position().x + root.size().width - child_size.width,
+        y: root.position().y + root.size().height - child_size.height,
     };
     child_1_pos -= padding as f32;
 
@@ -65,3 +66,16 @@ fn test_end_alignment() {
 }
 
 // TODO test overflow
+
+#[test]
+fn align_bottom_right() {
+    let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(40.0, 20.0));
+    let mut root = BlockLayout::new(child)
+        .intrinsic_size(IntrinsicSize::fixed(300.0, 200.0))
+        .padding(Padding::all(10.0))
+        .align(Alignment2D::BottomRight);
+
+    solve_layout(&mut root, Size::unit(500.0));
+
+    assert_eq!(root.child().position(), Position::new(250.0, 170.0));
+}
@@ -1,4 +1,7 @@
-use cascada::{BlockLayout, EmptyLayout, IntrinsicSize, Layout, Size, solve_layout};
+use cascada::{
+    BlockLayout, Border, Bounds, EmptyLayout, HitRegion, HorizontalLayout, IntrinsicSize, Layout,
+    LayoutError, Padding, PaddingValue, Position, RelativePadding, Size, solve_layout,
+};
 
 #[test]
 fn respect_child_max_width() {
@@ -12,3 +15,114 @@ fn respect_child_max_width() {
     solve_layout(&mut root, window);
     assert_eq!(root.children()[0].size().width, 20.0);
 }
+
+#[test]
+fn from_boxed_child() {
+    let window = Size::new(800.0, 800.0);
+    let child: Box<dyn Layout> =
+        Box::new(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(20.0, 40.0)));
+
+    let mut root = BlockLayout::from_boxed(child).padding(Padding::all(10.0));
+
+    solve_layout(&mut root, window);
+    assert_eq!(root.size(), Size::new(40.0, 60.0));
+    assert_eq!(root.child().size(), Size::new(20.0, 40.0));
+}
+
+#[test]
+fn computed_width_from_available_space() {
+    let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fill());
+    let block = BlockLayout::new(child)
+        .padding(Padding::all(10.0))
+        .computed_width(|available| available / 2.0 - 20.0);
+    let mut root = HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize::fixed(800.0, 200.0))
+        .add_child(block);
+
+    let errors = solve_layout(&mut root, Size::unit(1000.0));
+
+    let block = &root.children()[0];
+    assert!(errors.is_empty());
+    assert_eq!(block.size().width, 380.0);
+    assert_eq!(block.children()[0].size().width, 360.0);
+}
+
+#[test]
+fn percent_padding_from_parent_width() {
+    let block = BlockLayout::new(EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()))
+        .intrinsic_size(IntrinsicSize::fill())
+        .padding(RelativePadding::horizontal(PaddingValue::Percent(10.0)));
+    let mut root = HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize::fixed(800.0, 200.0))
+        .add_child(block);
+
+    solve_layout(&mut root, Size::unit(1000.0));
+
+    let block = &root.children()[0];
+    let padding = block.get_padding();
+    assert_eq!(padding.left, 80.0);
+    assert_eq!(padding.right, 80.0);
+    assert_eq!(block.children()[0].position().x, 80.0);
+    assert_eq!(block.children()[0].size(), Size::new(640.0, 200.0));
+}
+
+#[test]
+fn hit_regions_of_padded_block() {
+    let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 100.0));
+    let mut block = BlockLayout::new(child)
+        .padding(Padding::all(20.0))
+        .border(Border::all(2.0));
+    solve_layout(&mut block, Size::unit(500.0));
+
+    let content = Bounds::new(Position::unit(22.0), Size::unit(100.0));
+    assert_eq!(block.content_bounds(), content);
+    assert_eq!(block.hit_region(Position::unit(50.0)), HitRegion::Content);
+    assert_eq!(
+        block.hit_region(Position::new(10.0, 50.0)),
+        HitRegion::Padding
+    );
+    assert_eq!(
+        block.hit_region(Position::new(1.0, 50.0)),
+        HitRegion::Padding
+    );
+    assert_eq!(
+        block.hit_region(Position::new(130.0, 130.0)),
+        HitRegion::Padding
+    );
+    assert_eq!(
+        block.hit_region(Position::new(200.0, 50.0)),
+        HitRegion::Outside
+    );
+}
+
+#[test]
+fn shrink_block_with_min_size() {
+    let mut block = BlockLayout::new(EmptyLayout::new()).min_size(Size::new(0.0, 100.0));
+    let mut root = HorizontalLayout::new().add_child(
+        BlockLayout::new(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 20.0)))
+            .min_size(Size::unit(100.0)),
+    );
+
+    solve_layout(&mut block, Size::unit(500.0));
+    solve_layout(&mut root, Size::unit(500.0));
+
+    assert_eq!(block.size(), Size::new(0.0, 100.0));
+    assert_eq!(root.children()[0].size(), Size::unit(100.0));
+    assert_eq!(root.size(), Size::unit(100.0));
+}
+
+#[test]
+fn report_child_overflow() {
+    let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(300.0, 50.0));
+    let mut block = BlockLayout::new(child).intrinsic_size(IntrinsicSize::fixed(100.0, 100.0));
+    let id = block.id();
+
+    let errors = solve_layout(&mut block, Size::unit(500.0));
+
+    let overflow: Vec<_> = errors
+        .iter()
+        .filter(|error| matches!(error, LayoutError::Overflow { .. }))
+        .collect();
+    assert_eq!(overflow.len(), 1);
+    assert!(matches!(overflow[0], LayoutError::Overflow { id: error_id, .. } if *error_id == id));
+}
@@ -0,0 +1,22 @@
+use cascada::{BlockLayout, EmptyLayout, HorizontalLayout, Layout, VerticalLayout};
+
+#[test]
+fn node_types_exported() {
+    let nodes: Vec<Box<dyn Layout>> = vec![
+        Box::new(EmptyLayout::new()),
+        Box::new(BlockLayout::new(EmptyLayout::new())),
+        Box::new(HorizontalLayout::new()),
+        Box::new(VerticalLayout::new()),
+    ];
+
+    let labels: Vec<String> = nodes.iter().map(|node| node.label()).collect();
+    assert_eq!(
+        labels,
+        [
+            "EmptyLayout",
+            "BlockLayout",
+            "HorizontalLayout",
+            "VerticalLayout"
+        ]
+    );
+}
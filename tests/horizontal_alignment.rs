@@ -72,3 +72,47 @@ fn horizontal_center_alignment() {
 }
 
 // TODO test overflow
+
+#[test]
+fn child_overrides_cross_axis_alignment() {
+    let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+    let mut root = HorizontalLayout::new()
+        .cross_axis_alignment(AxisAlignment::Start)
+        .padding(Padding::all(10.0))
+        .intrinsic_size(IntrinsicSize::fixed(300.0, 200.0))
+        .add_child(child.clone())
+        .add_child(child.clone().cross_self(AxisAlignment::End))
+        .add_child(child);
+
+    solve_layout(&mut root, Size::unit(500.0));
+
+    assert_eq!(root.children()[0].position().y, 10.0);
+    // The far edge of the child lines up with the content edge
+    let end = root.position().y + root.size().height - 10.0;
+    let child = &root.children()[1];
+    assert_eq!(child.position().y + child.size().height, end);
+    assert_eq!(root.children()[2].position().y, 10.0);
+}
+
+#[test]
+fn stretch_cross_axis() {
+    let mut root = HorizontalLayout::new()
+        .cross_axis_alignment(AxisAlignment::Stretch)
+        .padding(Padding::all(10.0))
+        .intrinsic_size(IntrinsicSize::fixed(300.0, 200.0))
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed_width(50.0)))
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0)))
+        .add_child(
+            HorizontalLayout::new()
+                .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(20.0, 20.0))),
+        );
+
+    solve_layout(&mut root, Size::unit(500.0));
+
+    assert_eq!(root.children()[0].size(), Size::new(50.0, 180.0));
+    assert_eq!(root.children()[1].size(), Size::new(50.0, 50.0));
+    assert_eq!(root.children()[2].size(), Size::new(20.0, 180.0));
+    for child in root.children() {
+        assert_eq!(child.position().y, 10.0);
+    }
+}
@@ -1,6 +1,7 @@
 use cascada::{
-    BlockLayout, BoxSizing, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, Padding, Size,
-    solve_layout,
+    AxisAlignment, BlockLayout, BoxSizing, CrossSizing, EmptyLayout, HorizontalLayout,
+    IntrinsicSize, Layout, LayoutError, Padding, Position, Size, VerticalLayout, solve_layout,
+    solve_layout_scaled, solve_layout_snapped, solve_layout_with_insets,
 };
 
 #[test]
@@ -101,7 +102,6 @@ fn flex_with_shrink() {
     empty_size.width -= child_1_size.width;
     empty_size.width -= spacing as f32;
     empty_size.width -= padding.horizontal_sum();
-    empty_size.height += padding.vertical_sum();
 
     let empty = &root.children()[1];
     assert_eq!(root.size(), root_size);
@@ -194,3 +194,498 @@ fn redistribute_max_width() {
     assert_eq!(node.children()[0].size().width, 20.0);
     assert_eq!(node.children()[1].size().width, width);
 }
+
+#[test]
+fn add_boxed_children() {
+    let window = Size::new(800.0, 400.0);
+    let mut children: Vec<Box<dyn Layout>> = vec![];
+    for _ in 0..4 {
+        children.push(Box::new(
+            EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()),
+        ));
+    }
+
+    let mut root = HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize::fill())
+        .add_boxed_child(Box::new(
+            EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()),
+        ))
+        .add_boxed_children(children);
+
+    solve_layout(&mut root, window);
+
+    assert_eq!(root.children().len(), 5);
+    for child in root.children() {
+        assert_eq!(child.size(), Size::new(160.0, 400.0));
+    }
+}
+
+#[test]
+fn boxed_children() {
+    let empty = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+    let row = HorizontalLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 20.0));
+    let mut root = HorizontalLayout::new().add_boxed_children(vec![empty.boxed(), row.boxed()]);
+
+    solve_layout(&mut root, Size::unit(400.0));
+
+    assert_eq!(root.children().len(), 2);
+    assert_eq!(root.children()[0].size(), Size::unit(50.0));
+    assert_eq!(root.children()[1].size(), Size::new(100.0, 20.0));
+    assert_eq!(root.size(), Size::new(150.0, 50.0));
+}
+
+#[test]
+fn clone_boxed_with_new_ids() {
+    let row = HorizontalLayout::new()
+        .spacing(12)
+        .padding(Padding::all(10.0))
+        .intrinsic_size(IntrinsicSize::fill())
+        .add_child(BlockLayout::new(
+            EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(20.0, 20.0)),
+        ))
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()));
+
+    let mut copy = row.clone_boxed();
+    let mut row = row;
+
+    solve_layout(&mut row, Size::unit(500.0));
+    solve_layout(copy.as_mut(), Size::unit(500.0));
+
+    assert_eq!(row.iter().count(), copy.iter().count());
+    for (original, copy) in row.iter().zip(copy.iter()) {
+        assert_ne!(original.id(), copy.id());
+        assert_eq!(original.label(), copy.label());
+        assert_eq!(original.size(), copy.size());
+        assert_eq!(original.position(), copy.position());
+    }
+}
+
+#[test]
+fn justify_fill_leaves_space_after_last_child() {
+    let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 50.0));
+    let mut root = HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize::fill())
+        .main_axis_alignment(AxisAlignment::End)
+        .spacing(10)
+        .justify_fill(true)
+        .add_children([child.clone(), child.clone(), child]);
+
+    solve_layout(&mut root, Size::new(800.0, 200.0));
+
+    let positions: Vec<f32> = root.children().iter().map(|c| c.position().x).collect();
+    assert_eq!(positions, [0.0, 110.0, 220.0]);
+
+    let last = &root.children()[2];
+    let leftover = root.size().width - (last.position().x + last.size().width);
+    assert_eq!(leftover, 480.0);
+}
+
+#[test]
+fn link_child_widths() {
+    let a = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(150.0, 50.0));
+    let b = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+    let c = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+    let (a_id, b_id) = (a.id(), b.id());
+    let mut root = HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize::fill())
+        .spacing(10)
+        .add_children([a, b, c])
+        .link_width(a_id, b_id);
+
+    solve_layout(&mut root, Size::unit(800.0));
+
+    assert_eq!(root.children()[1].size(), Size::new(150.0, 50.0));
+    assert_eq!(root.children()[1].position().x, 160.0);
+    assert_eq!(root.children()[2].position().x, 320.0);
+
+    let mut copy = root.clone_boxed();
+    solve_layout(copy.as_mut(), Size::unit(800.0));
+    assert_eq!(copy.children()[1].size().width, 150.0);
+}
+
+#[test]
+fn overflow_with_linked_width() {
+    let a = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(200.0, 50.0));
+    let b = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+    let (a_id, b_id) = (a.id(), b.id());
+    let mut root = HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize::fixed(300.0, 50.0))
+        .add_children([a, b])
+        .link_width(a_id, b_id);
+    let root_id = root.id();
+
+    let errors = solve_layout(&mut root, Size::unit(800.0));
+
+    assert_eq!(root.children()[1].size().width, 200.0);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0], LayoutError::Overflow { id, .. } if id == root_id));
+}
+
+#[test]
+fn cross_sizing_match_first() {
+    let mut root = HorizontalLayout::new()
+        .cross_sizing(CrossSizing::MatchFirst)
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 40.0)))
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 100.0)));
+
+    let errors = solve_layout(&mut root, Size::unit(500.0));
+    assert_eq!(root.size(), Size::new(100.0, 40.0));
+    assert!(errors.is_empty());
+
+    let mut root = HorizontalLayout::new()
+        .cross_sizing(CrossSizing::MatchLast)
+        .padding(Padding::all(10.0))
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 100.0)))
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 40.0)));
+
+    let errors = solve_layout(&mut root, Size::unit(500.0));
+    assert_eq!(root.size(), Size::new(120.0, 60.0));
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn cross_sizing_fixed() {
+    let mut root = HorizontalLayout::new()
+        .cross_sizing(CrossSizing::Fixed(30.0))
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 40.0)))
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 100.0)));
+
+    let errors = solve_layout(&mut root, Size::unit(500.0));
+    assert_eq!(root.size(), Size::new(100.0, 30.0));
+    assert!(errors.is_empty());
+
+    let mut root = HorizontalLayout::new().cross_sizing(CrossSizing::Fixed(30.0));
+    let errors = solve_layout(&mut root, Size::unit(500.0));
+    assert_eq!(root.size(), Size::new(0.0, 30.0));
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn cross_sizing_ignores_tall_outlier() {
+    let bar = |height: f32| EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(20.0, height));
+    let mut root = HorizontalLayout::new()
+        .cross_sizing(CrossSizing::SecondLargest)
+        .add_children([bar(40.0), bar(1000.0), bar(60.0), bar(50.0)]);
+
+    let errors = solve_layout(&mut root, Size::unit(2000.0));
+    assert_eq!(root.size(), Size::new(80.0, 60.0));
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn reposition_after_resize() {
+    let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 50.0));
+    let mut root = HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize::fill())
+        .spacing(10)
+        .add_children([child.clone(), child.clone(), child]);
+    solve_layout(&mut root, Size::unit(800.0));
+
+    root.children_mut()[0].set_size(Size::new(250.0, 50.0));
+    let errors = root.reposition();
+
+    assert!(errors.is_empty());
+    assert_eq!(root.children()[0].size(), Size::new(250.0, 50.0));
+    assert_eq!(root.children()[1].size(), Size::new(100.0, 50.0));
+    assert_eq!(root.children()[1].position().x, 260.0);
+    assert_eq!(root.children()[2].position().x, 370.0);
+}
+
+#[test]
+fn round_flex_fills_available_space() {
+    let mut root = HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize::fill())
+        .round_flex(true)
+        .add_children([
+            EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()),
+            EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()),
+            EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()),
+        ]);
+
+    solve_layout(&mut root, Size::new(1000.0, 500.0));
+
+    let widths: Vec<_> = root
+        .children()
+        .iter()
+        .map(|child| child.size().width)
+        .collect();
+    assert_eq!(widths, [334.0, 333.0, 333.0]);
+    assert_eq!(widths.iter().sum::<f32>(), 1000.0);
+    assert_eq!(root.children()[2].position().x, 667.0);
+}
+
+#[test]
+fn snapped_positions_are_integers() {
+    let row = || {
+        HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fill())
+            .main_axis_alignment(AxisAlignment::Center)
+            .cross_axis_alignment(AxisAlignment::Center)
+            .spacing(3)
+            .add_children([
+                EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(10.5, 7.3)),
+                EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()),
+                EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()),
+            ])
+    };
+    let mut root = VerticalLayout::new()
+        .intrinsic_size(IntrinsicSize::fill())
+        .padding(Padding::all(0.4))
+        .add_children([row(), row(), row()]);
+
+    solve_layout_snapped(&mut root, Size::new(999.0, 333.3));
+
+    for layout in root.iter() {
+        let (position, size) = (layout.position(), layout.size());
+        for value in [position.x, position.y, size.width, size.height] {
+            assert_eq!(value.fract(), 0.0, "{}", layout.summary());
+        }
+    }
+
+    let row = &root.children()[0];
+    let [first, second, third] = row.children() else {
+        panic!("Expected three children");
+    };
+    assert_eq!(first.bounds().x[1] + 3.0, second.position().x);
+    assert_eq!(second.bounds().x[1] + 3.0, third.position().x);
+}
+
+#[test]
+fn solve_scaled() {
+    let mut root = HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize {
+            width: BoxSizing::Flex(1),
+            height: BoxSizing::Shrink,
+        })
+        .padding(Padding::all(8.0))
+        .spacing(4)
+        .add_children([
+            EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0)),
+            EmptyLayout::new().intrinsic_size(IntrinsicSize {
+                width: BoxSizing::Flex(1),
+                height: BoxSizing::Fixed(10.0),
+            }),
+        ]);
+
+    solve_layout_scaled(&mut root, Size::new(1000.0, 800.0), 2.0);
+
+    let [fixed, flex] = root.children() else {
+        panic!("Expected two children");
+    };
+    assert_eq!(root.size(), Size::new(1000.0, 132.0));
+    assert_eq!(fixed.size(), Size::unit(100.0));
+    assert_eq!(fixed.position(), Position::unit(16.0));
+    assert_eq!(flex.position().x, 16.0 + 100.0 + 8.0);
+    assert_eq!(flex.size().width, 1000.0 - 16.0 - 100.0 - 8.0 - 16.0);
+}
+
+#[test]
+fn scaled_round_flex_is_whole_pixels() {
+    let mut root = HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize::fill())
+        .round_flex(true)
+        .add_children((0..3).map(|_| EmptyLayout::new().intrinsic_size(IntrinsicSize::fill())));
+
+    solve_layout_scaled(&mut root, Size::new(150.0, 150.0), 1.5);
+
+    let mut x = 0.0;
+    for child in root.children() {
+        let (size, position) = (child.size(), child.position());
+        assert_eq!(size.width.fract(), 0.0);
+        assert_eq!(position.x.fract(), 0.0);
+        assert_eq!(position.x, x);
+        x += size.width;
+    }
+    assert_eq!(x, 150.0);
+}
+
+#[test]
+fn equal_columns() {
+    let mut root = HorizontalLayout::columns(4).intrinsic_size(IntrinsicSize::fill());
+
+    solve_layout(&mut root, Size::new(1000.0, 200.0));
+
+    assert_eq!(root.children().len(), 4);
+    for (i, column) in root.children().iter().enumerate() {
+        assert_eq!(column.size(), Size::new(250.0, 200.0));
+        assert_eq!(column.position().x, i as f32 * 250.0);
+    }
+}
+
+#[test]
+fn spacing_skips_collapsed_children() {
+    let child = || EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+    let mut root = HorizontalLayout::new()
+        .spacing(10)
+        .add_child(child())
+        .add_child(
+            EmptyLayout::new()
+                .intrinsic_size(IntrinsicSize::fixed(30.0, 30.0))
+                .collapsed(true),
+        )
+        .add_child(child());
+
+    let errors = solve_layout(&mut root, Size::unit(500.0));
+
+    let children = root.children();
+    assert!(errors.is_empty());
+    assert_eq!(children[1].size(), Size::default());
+    assert_eq!(children[2].position().x, 60.0);
+    assert_eq!(root.size().width, 110.0);
+}
+
+#[test]
+fn collapsed_subtree_takes_no_space() {
+    let row = HorizontalLayout::new()
+        .padding(Padding::all(10.0))
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 50.0)))
+        .collapsed(true);
+    let mut root = HorizontalLayout::new()
+        .add_child(row)
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0)));
+
+    let errors = solve_layout(&mut root, Size::unit(500.0));
+
+    assert!(errors.is_empty());
+    assert_eq!(root.size(), Size::unit(50.0));
+    assert_eq!(root.children()[1].position().x, 0.0);
+    assert_eq!(root.flatten().len(), 2);
+}
+
+#[test]
+fn hidden_children_keep_their_space() {
+    let child = || EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+    let mut root = HorizontalLayout::new()
+        .spacing(10)
+        .add_child(child())
+        .add_child(
+            EmptyLayout::new()
+                .intrinsic_size(IntrinsicSize::fixed(30.0, 30.0))
+                .hidden(true),
+        )
+        .add_child(child());
+
+    solve_layout(&mut root, Size::unit(500.0));
+
+    let children = root.children();
+    assert_eq!(children[1].position().x, 60.0);
+    assert_eq!(children[2].position().x, 100.0);
+    assert_eq!(root.size().width, 150.0);
+}
+
+#[test]
+fn flex_child_not_below_min_width() {
+    let content = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 20.0));
+    let flex_child = HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize::fill())
+        .add_child(content);
+    let mut root = HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize::fixed(40.0, 40.0))
+        .report_overflow(false)
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(20.0, 20.0)))
+        .add_child(flex_child);
+
+    solve_layout(&mut root, Size::unit(500.0));
+
+    let flex_child = &root.children()[1];
+    assert_eq!(flex_child.constraints().max_width, Some(100.0));
+    assert_eq!(flex_child.size().width, 100.0);
+}
+
+#[test]
+fn frozen_subtree_keeps_solved_layout() {
+    let mut hud = VerticalLayout::new()
+        .intrinsic_size(IntrinsicSize::fixed(100.0, 50.0))
+        .frozen(true)
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()));
+    solve_layout(&mut hud, Size::unit(500.0));
+
+    hud.reset();
+    hud = hud.intrinsic_size(IntrinsicSize::fixed(200.0, 80.0));
+    solve_layout_with_insets(
+        &mut hud,
+        Size::unit(500.0),
+        Padding::new(20.0, 0.0, 10.0, 0.0),
+    );
+
+    assert_eq!(hud.size(), Size::new(100.0, 50.0));
+    assert_eq!(hud.children()[0].size(), Size::new(100.0, 50.0));
+    assert_eq!(hud.children()[0].position(), Position::new(20.0, 10.0));
+}
+
+#[test]
+fn frozen_child_moves_with_parent() {
+    let hud = HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize::fill())
+        .frozen(true)
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()));
+    let mut root = HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize::fill())
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()))
+        .add_child(hud);
+    solve_layout(&mut root, Size::unit(500.0));
+
+    root.reset();
+    solve_layout(&mut root, Size::unit(800.0));
+
+    let hud = &root.children()[1];
+    assert_eq!(hud.size(), Size::new(250.0, 500.0));
+    assert_eq!(hud.position().x, 400.0);
+    assert_eq!(hud.children()[0].position().x, 400.0);
+    assert_eq!(hud.children()[0].size(), Size::new(250.0, 500.0));
+}
+
+#[test]
+fn fractional_flex_factors() {
+    let child = |factor: f32| {
+        EmptyLayout::new().intrinsic_size(IntrinsicSize {
+            width: BoxSizing::FlexF(factor),
+            height: BoxSizing::Fixed(50.0),
+        })
+    };
+    let mut layout = HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize::fixed(500.0, 100.0))
+        .add_children([child(1.5), child(1.0)]);
+
+    solve_layout(&mut layout, Size::unit(1000.0));
+
+    let children = layout.children();
+    assert_eq!(children[0].size().width, 300.0);
+    assert_eq!(children[1].size().width, 200.0);
+    assert_eq!(children[1].position().x, 300.0);
+}
+
+#[test]
+fn flex_child_of_shrink_block_fills_free_space() {
+    let inner = EmptyLayout::new().intrinsic_size(IntrinsicSize::fill());
+    let block = BlockLayout::new(inner).padding(Padding::all(10.0));
+    let mut row = HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize::fill())
+        .cross_axis_alignment(AxisAlignment::Stretch)
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 50.0)))
+        .add_child(block);
+
+    let errors = solve_layout(&mut row, Size::new(500.0, 300.0));
+
+    let block = &row.children()[1];
+    let inner = &block.children()[0];
+    assert!(errors.is_empty());
+    assert_eq!(block.size(), Size::new(400.0, 300.0));
+    assert_eq!(inner.size(), Size::new(380.0, 280.0));
+    assert_eq!(inner.position(), Position::new(110.0, 10.0));
+}
+
+#[test]
+fn shrink_block_shares_free_space_with_flex_siblings() {
+    let block = BlockLayout::new(EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()));
+    let mut row = HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize::fill())
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()))
+        .add_child(block);
+
+    solve_layout(&mut row, Size::new(500.0, 300.0));
+
+    assert_eq!(row.children()[0].size().width, 250.0);
+    assert_eq!(row.children()[1].size().width, 250.0);
+    assert_eq!(row.children()[1].children()[0].size().width, 250.0);
+}
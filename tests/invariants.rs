@@ -0,0 +1,243 @@
+use cascada::{
+    AxisAlignment, BlockLayout, BoxSizing, CrossSizing, EmptyLayout, HorizontalLayout,
+    IntrinsicSize, Layout, LayoutError, Padding, Size, VerticalLayout, solve_layout,
+};
+use proptest::prelude::*;
+
+/// A description of a layout tree, which is built into the real nodes.
+#[derive(Debug, Clone)]
+enum Node {
+    Empty(IntrinsicSize),
+    Block(IntrinsicSize, Padding, AxisAlignment, Box<Node>),
+    Horizontal(
+        IntrinsicSize,
+        Padding,
+        u32,
+        AxisAlignment,
+        CrossSizing,
+        Vec<Node>,
+    ),
+    Vertical(
+        IntrinsicSize,
+        Padding,
+        u32,
+        AxisAlignment,
+        CrossSizing,
+        Vec<Node>,
+    ),
+}
+
+impl Node {
+    /// Replace the intrinsic size of the node.
+    fn with_size(self, size: IntrinsicSize) -> Self {
+        match self {
+            Node::Empty(_) => Node::Empty(size),
+            Node::Block(_, padding, alignment, child) => {
+                Node::Block(size, padding, alignment, child)
+            }
+            Node::Horizontal(_, padding, spacing, alignment, cross_sizing, children) => {
+                Node::Horizontal(size, padding, spacing, alignment, cross_sizing, children)
+            }
+            Node::Vertical(_, padding, spacing, alignment, cross_sizing, children) => {
+                Node::Vertical(size, padding, spacing, alignment, cross_sizing, children)
+            }
+        }
+    }
+
+    fn build(self) -> Box<dyn Layout> {
+        match self {
+            Node::Empty(size) => Box::new(EmptyLayout::new().intrinsic_size(size)),
+            Node::Block(size, padding, alignment, child) => Box::new(
+                BlockLayout::from_boxed(child.build())
+                    .intrinsic_size(size)
+                    .padding(padding)
+                    .main_axis_alignment(alignment)
+                    .cross_axis_alignment(alignment),
+            ),
+            Node::Horizontal(size, padding, spacing, alignment, cross_sizing, children) => {
+                Box::new(
+                    HorizontalLayout::with_children(
+                        children.into_iter().map(Node::build).collect(),
+                    )
+                    .intrinsic_size(size)
+                    .padding(padding)
+                    .spacing(spacing)
+                    .main_axis_alignment(alignment)
+                    .cross_axis_alignment(alignment)
+                    .cross_sizing(cross_sizing),
+                )
+            }
+            Node::Vertical(size, padding, spacing, alignment, cross_sizing, children) => Box::new(
+                VerticalLayout::with_children(children.into_iter().map(Node::build).collect())
+                    .intrinsic_size(size)
+                    .padding(padding)
+                    .spacing(spacing)
+                    .main_axis_alignment(alignment)
+                    .cross_axis_alignment(alignment)
+                    .cross_sizing(cross_sizing),
+            ),
+        }
+    }
+}
+
+fn box_sizing() -> impl Strategy<Value = BoxSizing> {
+    prop_oneof![
+        (0.0f32..400.0).prop_map(BoxSizing::Fixed),
+        Just(BoxSizing::Shrink),
+        (1u8..4).prop_map(BoxSizing::Flex),
+    ]
+}
+
+fn intrinsic_size() -> impl Strategy<Value = IntrinsicSize> {
+    (box_sizing(), box_sizing()).prop_map(|(width, height)| IntrinsicSize { width, height })
+}
+
+fn padding() -> impl Strategy<Value = Padding> {
+    (0.0f32..40.0, 0.0f32..40.0, 0.0f32..40.0, 0.0f32..40.0)
+        .prop_map(|(left, right, top, bottom)| Padding::new(left, right, top, bottom))
+}
+
+fn alignment() -> impl Strategy<Value = AxisAlignment> {
+    prop_oneof![
+        Just(AxisAlignment::Start),
+        Just(AxisAlignment::Center),
+        Just(AxisAlignment::End),
+        Just(AxisAlignment::Stretch),
+    ]
+}
+
+fn cross_sizing() -> impl Strategy<Value = CrossSizing> {
+    prop_oneof![
+        Just(CrossSizing::Max),
+        Just(CrossSizing::MatchFirst),
+        Just(CrossSizing::MatchLast),
+        (0.0f32..400.0).prop_map(CrossSizing::Fixed),
+        Just(CrossSizing::SecondLargest),
+        (0.0f32..100.0).prop_map(CrossSizing::Percentile),
+    ]
+}
+
+/// A horizontal or vertical layout containing arbitrary children.
+fn container() -> impl Strategy<Value = Node> {
+    (
+        padding(),
+        0u32..40,
+        alignment(),
+        cross_sizing(),
+        prop::collection::vec(node(), 0..5),
+        any::<bool>(),
+    )
+        .prop_map(
+            |(padding, spacing, alignment, cross_sizing, children, horizontal)| {
+                let size = IntrinsicSize::default();
+                if horizontal {
+                    Node::Horizontal(size, padding, spacing, alignment, cross_sizing, children)
+                } else {
+                    Node::Vertical(size, padding, spacing, alignment, cross_sizing, children)
+                }
+            },
+        )
+}
+
+fn node() -> impl Strategy<Value = Node> {
+    let leaf = intrinsic_size().prop_map(Node::Empty);
+    leaf.prop_recursive(4, 32, 4, |inner| {
+        prop_oneof![
+            (intrinsic_size(), padding(), alignment(), inner.clone()).prop_map(
+                |(size, padding, alignment, child)| {
+                    Node::Block(size, padding, alignment, Box::new(child))
+                }
+            ),
+            (
+                intrinsic_size(),
+                padding(),
+                0u32..40,
+                alignment(),
+                cross_sizing(),
+                prop::collection::vec(inner.clone(), 0..4)
+            )
+                .prop_map(
+                    |(size, padding, spacing, alignment, cross_sizing, children)| {
+                        Node::Horizontal(size, padding, spacing, alignment, cross_sizing, children)
+                    }
+                ),
+            (
+                intrinsic_size(),
+                padding(),
+                0u32..40,
+                alignment(),
+                cross_sizing(),
+                prop::collection::vec(inner, 0..4)
+            )
+                .prop_map(
+                    |(size, padding, spacing, alignment, cross_sizing, children)| {
+                        Node::Vertical(size, padding, spacing, alignment, cross_sizing, children)
+                    }
+                ),
+        ]
+    })
+}
+
+proptest! {
+    #[test]
+    fn solved_geometry_is_finite(node in node(), width in 0.0f32..2000.0, height in 0.0f32..2000.0) {
+        let mut root = node.build();
+        solve_layout(root.as_mut(), Size::new(width, height));
+
+        for layout in root.iter() {
+            let (size, position) = (layout.size(), layout.position());
+            prop_assert!(size.width.is_finite() && size.height.is_finite(), "{}", layout.summary());
+            prop_assert!(position.x.is_finite() && position.y.is_finite(), "{}", layout.summary());
+        }
+    }
+
+    #[test]
+    fn min_constraints_within_max(node in node(), width in 0.0f32..2000.0, height in 0.0f32..2000.0) {
+        let mut root = node.build();
+        solve_layout(root.as_mut(), Size::new(width, height));
+
+        // The root's max constraints come from the window, which it
+        // may not fit in
+        for layout in root.iter().skip(1) {
+            let constraints = layout.constraints();
+            prop_assert!(constraints.min_width >= 0.0, "{}", layout.summary());
+            prop_assert!(constraints.min_height >= 0.0, "{}", layout.summary());
+            // Flex nodes can be squeezed below their minimum size when
+            // there isn't enough space
+            let flex = matches!(layout.get_intrinsic_size().height, BoxSizing::Flex(_) | BoxSizing::FlexF(_));
+            if !flex {
+                prop_assert!(constraints.min_height <= constraints.max_height, "{}", layout.summary());
+            }
+        }
+    }
+
+    #[test]
+    fn solving_is_repeatable(node in node(), width in 0.0f32..2000.0, height in 0.0f32..2000.0) {
+        let mut root = node.build();
+        solve_layout(root.as_mut(), Size::new(width, height));
+        let first: Vec<_> = root.iter().map(|layout| layout.bounds()).collect();
+
+        root.reset();
+        solve_layout(root.as_mut(), Size::new(width, height));
+        let second: Vec<_> = root.iter().map(|layout| layout.bounds()).collect();
+
+        prop_assert_eq!(first, second);
+    }
+
+    #[test]
+    fn sized_to_min_never_overflows(node in container()) {
+        let mut root = node.clone().build();
+        solve_layout(root.as_mut(), Size::unit(2000.0));
+        let constraints = root.constraints();
+
+        let size = IntrinsicSize::fixed(constraints.min_width, constraints.min_height);
+        let mut root = node.with_size(size).build();
+        let errors = solve_layout(root.as_mut(), Size::unit(2000.0));
+
+        let id = root.id();
+        let overflow = errors
+            .iter()
+            .any(|error| matches!(error, LayoutError::Overflow { id: node, .. } if *node == id));
+        prop_assert!(!overflow, "{}", root.summary());
+    }
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "testing")]
+use cascada::testing::{assert_layout_snapshot, snapshot, snapshot_with_ids};
+use cascada::{
+    BlockLayout, EmptyLayout, HorizontalLayout, IdScope, IntrinsicSize, Padding, Size, solve_layout,
+};
+
+fn row() -> HorizontalLayout {
+    HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize::fill())
+        .padding(Padding::all(10.0))
+        .spacing(20)
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 50.0)))
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()))
+        .add_child(BlockLayout::new(
+            EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(40.0, 40.0)),
+        ))
+}
+
+#[test]
+fn row_snapshot() {
+    let mut layout = row();
+    solve_layout(&mut layout, Size::new(500.0, 200.0));
+    assert_layout_snapshot(&layout, "row");
+}
+
+#[test]
+fn snapshots_are_deterministic() {
+    let mut first = row();
+    let mut second = row();
+    solve_layout(&mut first, Size::new(500.0, 200.0));
+    solve_layout(&mut second, Size::new(500.0, 200.0));
+    assert_eq!(snapshot(&first), snapshot(&second));
+}
+
+#[test]
+fn scoped_snapshots_with_ids() {
+    let build = || {
+        let _scope = IdScope::new();
+        let mut layout = row();
+        solve_layout(&mut layout, Size::new(500.0, 200.0));
+        layout
+    };
+
+    assert_eq!(snapshot_with_ids(&build()), snapshot_with_ids(&build()));
+}
@@ -0,0 +1,30 @@
+use cascada::{
+    BlockLayout, CustomLayout, EmptyLayout, HorizontalLayout, IntrinsicSize, Layout, Size,
+    solve_layout,
+};
+use std::sync::mpsc;
+use std::thread;
+
+#[test]
+fn solve_on_worker_thread() {
+    let block = BlockLayout::new(EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()))
+        .computed_width(|width| width / 2.0);
+    let custom = CustomLayout::new().on_size(|_| Size::unit(50.0));
+    let root: Box<dyn Layout> = HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize::fill())
+        .add_child(block)
+        .add_child(custom)
+        .boxed();
+
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut root = root;
+        let errors = solve_layout(root.as_mut(), Size::unit(500.0));
+        sender.send((root, errors)).unwrap();
+    });
+
+    let (root, errors) = receiver.recv().unwrap();
+    assert!(errors.is_empty());
+    assert_eq!(root.size(), Size::unit(500.0));
+    assert_eq!(root.children()[1].size(), Size::unit(50.0));
+}
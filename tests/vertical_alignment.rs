@@ -76,3 +76,37 @@ fn test_center_alignment() {
 }
 
 // TODO test overflow
+
+#[test]
+fn stretch_cross_axis() {
+    let mut root = VerticalLayout::new()
+        .cross_axis_alignment(AxisAlignment::Stretch)
+        .intrinsic_size(IntrinsicSize::fixed(300.0, 200.0))
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed_height(50.0)))
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0)));
+
+    solve_layout(&mut root, Size::unit(500.0));
+
+    assert_eq!(root.children()[0].size(), Size::new(300.0, 50.0));
+    assert_eq!(root.children()[1].size(), Size::new(50.0, 50.0));
+}
+
+#[test]
+fn child_overrides_cross_axis_alignment() {
+    let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+    let mut root = VerticalLayout::new()
+        .cross_axis_alignment(AxisAlignment::Start)
+        .padding(Padding::all(10.0))
+        .intrinsic_size(IntrinsicSize::fixed(300.0, 200.0))
+        .add_child(child.clone())
+        .add_child(child.cross_self(AxisAlignment::End));
+
+    let errors = solve_layout(&mut root, Size::unit(500.0));
+
+    // The far edge of the child lines up with the content edge
+    let end = root.position().x + root.size().width - 10.0;
+    let child = &root.children()[1];
+    assert!(errors.is_empty());
+    assert_eq!(root.children()[0].position().x, 10.0);
+    assert_eq!(child.position().x + child.size().width, end);
+}
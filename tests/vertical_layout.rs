@@ -1,5 +1,7 @@
 use cascada::{
-    BoxSizing, EmptyLayout, IntrinsicSize, Layout, Padding, Size, VerticalLayout, solve_layout,
+    AxisAlignment, BlockLayout, Bounds, BoxSizing, CustomLayout, EmptyLayout, HorizontalLayout,
+    IntrinsicSize, Layout, LayoutError, MainAxisSize, Padding, Position, Size, SolveOptions,
+    VerticalLayout, solve_layout, solve_layout_culled,
 };
 
 #[test]
@@ -33,3 +35,306 @@ fn subtract_fixed_height_from_available_space() {
 
     assert_eq!(root.children()[2].size().height, flex_child_height)
 }
+
+#[test]
+fn with_mixed_children() {
+    let block =
+        BlockLayout::new(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0)))
+            .padding(Padding::all(10.0));
+    let empty = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 20.0));
+
+    let mut root = VerticalLayout::with_children(vec![Box::new(block), Box::new(empty)]);
+
+    solve_layout(&mut root, Size::unit(500.0));
+
+    assert_eq!(root.size(), Size::new(100.0, 90.0));
+    assert_eq!(root.children()[0].size(), Size::unit(70.0));
+    assert_eq!(root.children()[1].position().y, 70.0);
+}
+
+#[test]
+fn scroll_children() {
+    let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 100.0));
+    let mut root = VerticalLayout::new()
+        .spacing(10)
+        .add_children([child.clone(), child]);
+
+    solve_layout(&mut root, Size::unit(500.0));
+    assert_eq!(root.children()[1].position().y, 110.0);
+
+    root.scroll(-50.0);
+    root.scroll(-25.0);
+    solve_layout(&mut root, Size::unit(500.0));
+
+    assert_eq!(root.scroll_offset(), -75.0);
+    assert_eq!(root.children()[0].position().y, -75.0);
+    assert_eq!(root.children()[1].position().y, 35.0);
+}
+
+#[test]
+fn disable_overflow_reporting() {
+    let child = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(200.0, 20.0));
+    let marquee = HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize::fixed(100.0, 20.0))
+        .report_overflow(false)
+        .add_child(child.clone());
+    let row = HorizontalLayout::new()
+        .intrinsic_size(IntrinsicSize::fixed(100.0, 20.0))
+        .add_child(child);
+    let row_id = row.id();
+    let mut root = VerticalLayout::new().add_child(marquee).add_child(row);
+
+    let errors: Vec<_> = solve_layout(&mut root, Size::unit(500.0))
+        .into_iter()
+        .filter(|error| matches!(error, LayoutError::Overflow { .. }))
+        .collect();
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0], LayoutError::Overflow { id, .. } if id == row_id));
+}
+
+#[test]
+fn overflow_amount_of_list() {
+    let padding = Padding::all(10.0);
+    let spacing = 5;
+    let rows: Vec<_> = (0..10)
+        .map(|_| EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 40.0)))
+        .collect();
+    let mut list = VerticalLayout::new()
+        .intrinsic_size(IntrinsicSize::fixed(120.0, 200.0))
+        .padding(padding)
+        .spacing(spacing)
+        .add_children(rows);
+
+    solve_layout(&mut list, Size::unit(1000.0));
+
+    let mut content_height = 10.0 * 40.0 + 9.0 * spacing as f32;
+    content_height += padding.vertical_sum();
+    assert_eq!(
+        list.overflow_amount(),
+        Size::new(0.0, content_height - 200.0)
+    );
+}
+
+#[test]
+fn spacing_skips_collapsed_children() {
+    let child = || EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+    let collapsed = EmptyLayout::new()
+        .intrinsic_size(IntrinsicSize::fixed(50.0, 20.0))
+        .collapsed(true);
+    let mut root = VerticalLayout::new()
+        .spacing(10)
+        .main_axis_alignment(AxisAlignment::Center)
+        .intrinsic_size(IntrinsicSize::fixed(100.0, 200.0))
+        .add_child(child())
+        .add_child(collapsed)
+        .add_child(child());
+
+    let errors = solve_layout(&mut root, Size::unit(500.0));
+
+    let children = root.children();
+    let first_end = children[0].position().y + children[0].size().height;
+    assert!(errors.is_empty());
+    assert_eq!(children[0].position().y, 45.0);
+    assert_eq!(children[1].size(), Size::default());
+    assert_eq!(children[2].position().y - first_end, 10.0);
+}
+
+#[test]
+fn min_main_axis_size() {
+    let child = || EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+    let column = VerticalLayout::new()
+        .intrinsic_size(IntrinsicSize::fill())
+        .main_axis_size(MainAxisSize::Min)
+        .spacing(10)
+        .add_children([child(), child()]);
+    let mut root = VerticalLayout::new()
+        .intrinsic_size(IntrinsicSize::fixed(200.0, 400.0))
+        .main_axis_alignment(AxisAlignment::Center)
+        .add_child(column);
+
+    let errors = solve_layout(&mut root, Size::unit(1000.0));
+
+    let column = &root.children()[0];
+    assert!(errors.is_empty());
+    assert_eq!(column.size(), Size::new(200.0, 110.0));
+    assert_eq!(column.position().y, 145.0);
+    assert_eq!(column.children()[1].position().y, 205.0);
+}
+
+#[test]
+fn child_at_main_offset() {
+    let item = || EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(200.0, 100.0));
+    let mut root = VerticalLayout::new()
+        .intrinsic_size(IntrinsicSize::fill())
+        .add_children((0..5).map(|_| item()));
+
+    solve_layout(&mut root, Size::unit(1000.0));
+
+    let third = root.children()[2].id();
+    assert_eq!(root.child_at_main_offset(250.0), Some(third));
+    assert_eq!(root.child_at_main_offset(600.0), None);
+}
+
+#[test]
+fn cull_rows_outside_viewport() {
+    let row = || {
+        HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fixed(500.0, 100.0))
+            .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0)))
+    };
+    let mut root = VerticalLayout::new().add_children((0..100).map(|_| row()));
+
+    let options = SolveOptions {
+        viewport: Some(Bounds::new(Position::default(), Size::new(500.0, 250.0))),
+        ..Default::default()
+    };
+    let (_, culled) = solve_layout_culled(&mut root, Size::new(500.0, 1000.0), options);
+
+    let rows = root.children();
+    let expected: Vec<_> = rows[3..].iter().map(|row| row.id()).collect();
+    assert_eq!(culled, expected);
+    assert_eq!(rows[2].children()[0].position().y, 200.0);
+    assert_eq!(rows[50].position().y, 5000.0);
+    assert_eq!(rows[50].children()[0].position(), Position::default());
+}
+
+#[test]
+fn panicking_culled_solve_stops_culling() {
+    let row = || {
+        HorizontalLayout::new()
+            .intrinsic_size(IntrinsicSize::fixed(500.0, 100.0))
+            .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0)))
+    };
+    let options = SolveOptions {
+        viewport: Some(Bounds::new(Position::default(), Size::unit(100.0))),
+        ..Default::default()
+    };
+    let result = std::panic::catch_unwind(|| {
+        let mut root = VerticalLayout::new()
+            .add_child(row())
+            .add_child(CustomLayout::new().on_size(|_| panic!("user layout failed")));
+        solve_layout_culled(&mut root, Size::unit(500.0), options)
+    });
+    assert!(result.is_err());
+
+    let mut root = VerticalLayout::new().add_children([row(), row()]);
+    solve_layout(&mut root, Size::unit(500.0));
+    assert_eq!(root.children()[1].children()[0].position().y, 100.0);
+}
+
+#[test]
+fn stretch_shrink_children_to_column_width() {
+    let card =
+        BlockLayout::new(EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0)));
+    let fixed = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(100.0, 50.0));
+    let mut root = VerticalLayout::new()
+        .intrinsic_size(IntrinsicSize::fixed_width(300.0))
+        .cross_axis_alignment(AxisAlignment::Center)
+        .stretch_cross_axis(true)
+        .add_child(card)
+        .add_child(EmptyLayout::new())
+        .add_child(fixed);
+
+    solve_layout(&mut root, Size::unit(1000.0));
+
+    let children = root.children();
+    assert_eq!(children[0].size(), Size::new(300.0, 50.0));
+    assert_eq!(children[0].position().x, 0.0);
+    assert_eq!(children[1].size().width, 300.0);
+    assert_eq!(children[2].size().width, 100.0);
+    assert_eq!(children[2].position().x, 100.0);
+}
+
+#[test]
+fn flex_child_of_stretched_block_fills_block() {
+    let inner = EmptyLayout::new().intrinsic_size(IntrinsicSize::fill());
+    let block = BlockLayout::new(inner).padding(Padding::all(10.0));
+    let mut column = VerticalLayout::new()
+        .intrinsic_size(IntrinsicSize::fill())
+        .cross_axis_alignment(AxisAlignment::Stretch)
+        .add_child(block);
+
+    solve_layout(&mut column, Size::new(500.0, 300.0));
+
+    let block = &column.children()[0];
+    assert_eq!(block.size(), Size::new(500.0, 20.0));
+    assert_eq!(block.children()[0].size(), Size::new(480.0, 0.0));
+}
+
+#[test]
+fn solve_with_infinite_height() {
+    let item = || EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(200.0, 100.0));
+    let mut root = VerticalLayout::new()
+        .intrinsic_size(IntrinsicSize::fill())
+        .spacing(10)
+        .add_children([item(), item(), item()])
+        .add_child(EmptyLayout::new().intrinsic_size(IntrinsicSize::fill()));
+
+    let errors = solve_layout(&mut root, Size::new(500.0, f32::INFINITY));
+
+    assert!(errors.is_empty());
+    assert_eq!(root.size(), Size::new(500.0, 330.0));
+    let flex = &root.children()[3];
+    assert_eq!(flex.size(), Size::new(500.0, 0.0));
+    assert_eq!(flex.position().y, 330.0);
+}
+
+#[test]
+fn restore_scroll_after_items_change() {
+    let list = |count: usize| {
+        let item = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(200.0, 100.0));
+        VerticalLayout::new()
+            .intrinsic_size(IntrinsicSize::fixed(200.0, 300.0))
+            .report_overflow(false)
+            .add_children((0..count).map(|_| item.clone()))
+    };
+
+    let mut layout = list(10);
+    layout.scroll(-650.0);
+    solve_layout(&mut layout, Size::unit(1000.0));
+    let state = layout.scroll_state();
+
+    // Adding items keeps the offset
+    let mut layout = list(15);
+    solve_layout(&mut layout, Size::unit(1000.0));
+    layout.restore_scroll(state);
+    layout.reposition();
+    assert_eq!(layout.scroll_offset(), -650.0);
+    assert_eq!(layout.children()[0].position().y, -650.0);
+
+    // Removing items clamps the offset to the new end of the content
+    let mut layout = list(5);
+    solve_layout(&mut layout, Size::unit(1000.0));
+    layout.restore_scroll(state);
+    layout.reposition();
+    assert_eq!(layout.scroll_offset(), -200.0);
+    assert_eq!(layout.children()[4].position().y, 200.0);
+}
+
+#[test]
+fn link_widths_across_rows() {
+    let a = EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(300.0, 50.0));
+    let inner = EmptyLayout::new().intrinsic_size(IntrinsicSize {
+        width: BoxSizing::Flex(1),
+        height: BoxSizing::Fixed(20.0),
+    });
+    let b = BlockLayout::new(inner).padding(Padding::all(10.0));
+    let (a_id, b_id) = (a.id(), b.id());
+    let item = || EmptyLayout::new().intrinsic_size(IntrinsicSize::fixed(50.0, 50.0));
+
+    let mut root = VerticalLayout::new()
+        .add_child(HorizontalLayout::new().add_child(a).add_child(item()))
+        .add_child(HorizontalLayout::new().add_child(b).add_child(item()))
+        .link_width(a_id, b_id);
+
+    let errors = solve_layout(&mut root, Size::unit(800.0));
+
+    let row = &root.children()[1];
+    let block = &row.children()[0];
+    assert!(errors.is_empty());
+    assert_eq!(block.size().width, 300.0);
+    assert_eq!(block.children()[0].size().width, 280.0);
+    assert_eq!(row.children()[1].position().x, 300.0);
+    assert_eq!(row.size().width, 350.0);
+}